@@ -6,6 +6,9 @@ import {
   getAssociatedTokenAddressSync,
 } from '@solana/spl-token'
 import { sha256 } from '@noble/hashes/sha256'
+import { DEFAULT_OPERATOR_THRESHOLD_BPS, getOperatorThreshold } from './operatorThreshold'
+
+export { DEFAULT_OPERATOR_THRESHOLD_BPS, getOperatorThreshold }
 
 /**
  * ORAO VRF (Classic VRF program id; devnet/mainnet)
@@ -21,6 +24,10 @@ export const ORAO_VRF_PROGRAM_ID = new PublicKey(
 const ORAO_CONFIG_SEED = 'orao-vrf-network-configuration'
 const ORAO_RANDOMNESS_SEED = 'orao-vrf-randomness-request'
 
+const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey(
+  'BPFLoaderUpgradeab1e11111111111111111111111'
+)
+
 // ORAO RandomnessV2 layout (borsh via Anchor):
 // discriminator(8) + variant(u8) + client(32) + seed(32) + randomness(64)  [Fulfilled]
 // discriminator(8) + variant(u8) + client(32) + seed(32) + responses(vec)  [Pending]
@@ -386,6 +393,14 @@ export function deriveGlobalStatePdas(programId: PublicKey, usdcMint: PublicKey)
   return { globalState, globalVaultUsdc }
 }
 
+export function deriveGovDepositPda(programId: PublicKey, table: PublicKey, depositor: PublicKey) {
+  const [govDeposit] = PublicKey.findProgramAddressSync(
+    [Buffer.from('gov_deposit'), table.toBuffer(), depositor.toBuffer()],
+    programId
+  )
+  return govDeposit
+}
+
 export async function getGlobalLiquidity(
   provider: anchor.AnchorProvider,
   programId: PublicKey,
@@ -425,6 +440,15 @@ export async function getGlobalLiquidity(
   return { globalState, globalVaultUsdc, vaultBalance, lockedLiability, available }
 }
 
+// init/repair are restricted to the program's upgrade authority, read from its ProgramData
+function deriveProgramDataPda(programId: PublicKey) {
+  const [programData] = PublicKey.findProgramAddressSync(
+    [programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+  )
+  return programData
+}
+
 async function ensureGlobalInitialized(
   program: anchor.Program,
   provider: anchor.AnchorProvider,
@@ -432,6 +456,7 @@ async function ensureGlobalInitialized(
 ) {
   const payer = provider.wallet.publicKey
   const { globalState, globalVaultUsdc } = deriveGlobalStatePdas(program.programId, usdcMint)
+  const programData = deriveProgramDataPda(program.programId)
 
   const info = await provider.connection.getAccountInfo(globalState, 'confirmed')
   if (info) {
@@ -447,6 +472,9 @@ async function ensureGlobalInitialized(
           usdc_mint: usdcMint as any,
           globalState,
           global_state: globalState as any,
+          program: program.programId,
+          programData,
+          program_data: programData as any,
           globalVaultUsdc,
           global_vault_usdc: globalVaultUsdc as any,
           systemProgram: SystemProgram.programId,
@@ -468,6 +496,9 @@ async function ensureGlobalInitialized(
       usdc_mint: usdcMint as any,
       globalState,
       global_state: globalState as any,
+      program: program.programId,
+      programData,
+      program_data: programData as any,
       globalVaultUsdc,
       global_vault_usdc: globalVaultUsdc as any,
       systemProgram: SystemProgram.programId,
@@ -489,8 +520,10 @@ export async function createTable(
     usdcMint: string
     govMint: string
     mode: number
+    variant?: 'european' | 'american' | 'laPartage' | 'enPrison'
     minBet: number
     maxBet: number
+    operatorThresholdBps?: number
   }
 ) {
   const payer = provider.wallet.publicKey
@@ -499,6 +532,7 @@ export async function createTable(
   const mode = params.mode
   const minB = new anchor.BN(params.minBet)
   const maxB = new anchor.BN(params.maxBet)
+  const thresholdBps = params.operatorThresholdBps ?? DEFAULT_OPERATOR_THRESHOLD_BPS
 
   const usdcMintPk = new PublicKey(params.usdcMint)
   const govMintPk = new PublicKey(params.govMint)
@@ -518,9 +552,11 @@ export async function createTable(
 
   // Frontend passes mode as number; program expects TableMode enum
   const modeEnum: any = mode === 1 ? { public: {} } : { private: {} }
+  // Program expects TableVariant enum; defaults to a single-zero wheel
+  const variantEnum: any = { [params.variant ?? 'european']: {} }
 
   const txSig = await program.methods
-    .createTable(seedBn, modeEnum, minB, maxB)
+    .createTable(seedBn, modeEnum, variantEnum, minB, maxB, thresholdBps)
     .accounts({
       creator: payer,
       usdcMint: usdcMintPk,
@@ -550,7 +586,6 @@ export async function depositLiquidity(
     table: PublicKey
     amount: number
     operatorUsdcAta?: PublicKey
-    operatorGovAta?: PublicKey
   }
 ) {
   const operator = provider.wallet.publicKey
@@ -561,8 +596,8 @@ export async function depositLiquidity(
     args.operatorUsdcAta ?? getAssociatedTokenAddressSync(usdcMintPk, operator)
 
   const govMintPk = tableAcc.govMint
-  const operatorGovAta =
-    args.operatorGovAta ?? getAssociatedTokenAddressSync(govMintPk, operator)
+  // The operator threshold is checked against deposited GOV, not the wallet balance
+  const operatorGovDeposit = deriveGovDepositPda(program.programId, args.table, operator)
 
   // The table stores the exact global_state address used by the program.
   // Ensure we are deriving/repairing the correct PDA for this table's USDC mint.
@@ -601,8 +636,10 @@ export async function depositLiquidity(
       operatorUsdcAta,
       operator_usdc_ata: operatorUsdcAta as any,
       table: args.table,
-      operatorGovAta,
-      operator_gov_ata: operatorGovAta as any,
+      operatorGovDeposit,
+      operator_gov_deposit: operatorGovDeposit as any,
+      govMint: govMintPk,
+      gov_mint: govMintPk as any,
       globalState,
       global_state: globalState as any,
       globalVaultUsdc,
@@ -642,6 +679,9 @@ export async function depositLiquidity(
             usdc_mint: usdcMintPk as any,
             globalState,
             global_state: globalState as any,
+            program: program.programId,
+            programData: deriveProgramDataPda(program.programId),
+            program_data: deriveProgramDataPda(program.programId) as any,
             globalVaultUsdc,
             global_vault_usdc: globalVaultUsdc as any,
             systemProgram: SystemProgram.programId,
@@ -678,7 +718,7 @@ export async function depositLiquidity(
       table: args.table.toBase58(),
       operator: operator.toBase58(),
       operatorUsdcAta: operatorUsdcAta.toBase58(),
-      operatorGovAta: operatorGovAta.toBase58(),
+      operatorGovDeposit: operatorGovDeposit.toBase58(),
       globalState: globalState.toBase58(),
       globalVaultUsdc: globalVaultUsdc.toBase58(),
       instructionAccounts: debugKeys,
@@ -696,7 +736,6 @@ export async function executeWithdraw(
     table: PublicKey
     amount: number
     operatorUsdcAta?: PublicKey
-    operatorGovAta?: PublicKey
   }
 ) {
   const operator = provider.wallet.publicKey
@@ -707,8 +746,7 @@ export async function executeWithdraw(
     args.operatorUsdcAta ?? getAssociatedTokenAddressSync(usdcMintPk, operator)
 
   const govMintPk = new PublicKey(tableAcc.govMint)
-  const operatorGovAta =
-    args.operatorGovAta ?? getAssociatedTokenAddressSync(govMintPk, operator)
+  const operatorGovDeposit = deriveGovDepositPda(program.programId, args.table, operator)
 
   const { globalState, globalVaultUsdc } = await ensureGlobalInitialized(program, provider, usdcMintPk)
 
@@ -718,7 +756,8 @@ export async function executeWithdraw(
       operator,
       operatorUsdcAta,
       table: args.table,
-      operatorGovAta,
+      operatorGovDeposit,
+      govMint: govMintPk,
       globalState,
       globalVaultUsdc,
       tokenProgram: TOKEN_PROGRAM_ID,
@@ -902,7 +941,10 @@ export async function getBet(
 export default {
   initProgram,
   deriveGlobalStatePdas,
+  deriveGovDepositPda,
   getGlobalLiquidity,
+  getOperatorThreshold,
+  DEFAULT_OPERATOR_THRESHOLD_BPS,
   getOraoRandomnessV2Fulfilled,
   createTable,
   depositLiquidity,
@@ -0,0 +1,21 @@
+import type { Commitment, Connection, PublicKey } from '@solana/web3.js'
+
+// Operator threshold used when creating tables (51% of GOV supply)
+export const DEFAULT_OPERATOR_THRESHOLD_BPS = 5_100
+
+// Raw GOV needed to operate: `bps` of `supply`, rounded up like the program
+export function operatorThresholdRaw(supply: bigint, bps: number): bigint {
+  return (supply * BigInt(bps) + 9_999n) / 10_000n
+}
+
+// GOV (ui units) needed to operate: `bps` of the GOV mint supply
+export async function getOperatorThreshold(
+  connection: Connection,
+  govMint: PublicKey,
+  bps: number,
+  commitment: Commitment = 'confirmed'
+): Promise<number> {
+  const supply = await connection.getTokenSupply(govMint, commitment)
+  const needed = operatorThresholdRaw(BigInt(supply.value.amount), bps)
+  return Number(needed) / 10 ** supply.value.decimals
+}
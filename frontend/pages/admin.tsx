@@ -28,8 +28,6 @@ type SocialLinks = {
   discord?: string
 }
 
-const OPERATOR_THRESHOLD = 51
-
 export default function Admin() {
   const [publicKey, setPublicKey] = useState<PublicKey | null>(null)
   const [govBalanceEnvUi, setGovBalanceEnvUi] = useState(0)
@@ -37,6 +35,8 @@ export default function Admin() {
 
   const [tableAddress, setTableAddress] = useState<string>(sanitizeFirstPda(process.env.NEXT_PUBLIC_TABLE_PDA) || '')
   const [isOperator, setIsOperator] = useState(false)
+  // GOV needed to operate the selected table (default share of supply when none is set)
+  const [operatorThreshold, setOperatorThreshold] = useState<number | null>(null)
 
   const rpcUrl = process.env.NEXT_PUBLIC_RPC_URL || 'https://api.devnet.solana.com'
   const connection = useMemo(() => new Connection(rpcUrl), [rpcUrl])
@@ -116,7 +116,44 @@ export default function Admin() {
     // eslint-disable-next-line react-hooks/exhaustive-deps
   }, [publicKey?.toBase58()])
 
-  const isFunctionalTokenOwner = govBalanceEnvUi >= OPERATOR_THRESHOLD
+  const isFunctionalTokenOwner = operatorThreshold !== null && govBalanceEnvUi >= operatorThreshold
+
+  // Operator threshold, read from the selected table
+  useEffect(() => {
+    ;(async () => {
+      try {
+        if (!publicKey) {
+          setOperatorThreshold(null)
+          return
+        }
+        const { program, provider } = await ensureProgram()
+        if (tableAddress) {
+          try {
+            const tableAcc: any = await (program.account as any).table.fetch(new PublicKey(tableAddress))
+            setOperatorThreshold(await anchorLib.getOperatorThreshold(
+              provider.connection,
+              new PublicKey(tableAcc.govMint),
+              Number(tableAcc.operatorThresholdBps)
+            ))
+            return
+          } catch {}
+        }
+        const mintStr = process.env.NEXT_PUBLIC_GOV_MINT
+        if (!mintStr) {
+          setOperatorThreshold(null)
+          return
+        }
+        setOperatorThreshold(await anchorLib.getOperatorThreshold(
+          provider.connection,
+          new PublicKey(mintStr),
+          anchorLib.DEFAULT_OPERATOR_THRESHOLD_BPS
+        ))
+      } catch {
+        setOperatorThreshold(null)
+      }
+    })()
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [publicKey?.toBase58(), tableAddress])
 
   // operator check for liquidity actions
   useEffect(() => {
@@ -154,7 +191,7 @@ export default function Admin() {
     setUiNotice('')
     try {
       if (!publicKey) return alert('Connect wallet first')
-      if (!isFunctionalTokenOwner) return alert('Need GOV >= 51')
+      if (!isFunctionalTokenOwner) return alert(`Need GOV >= ${operatorThreshold ?? '?'}`)
 
       const wallet = getProvider()
       if (!wallet?.signMessage) return alert('Wallet must support signMessage')
@@ -199,7 +236,7 @@ export default function Admin() {
             <div className="muted" style={{fontSize: 13}}>
               Wallet: {publicKey ? `${publicKey.toBase58().slice(0, 6)}…${publicKey.toBase58().slice(-6)}` : 'not connected'}
               <br />
-              GOV balance: {govBalanceEnvUi} (need {OPERATOR_THRESHOLD})
+              GOV balance: {govBalanceEnvUi} (need {operatorThreshold ?? '…'})
             </div>
             <div>
               {publicKey ? <button className="btn-secondary" onClick={disconnect}>Disconnect</button> : <button onClick={connect}>Connect</button>}
@@ -208,7 +245,7 @@ export default function Admin() {
 
           {!isFunctionalTokenOwner ? (
             <div className="notice notice-danger" style={{marginTop: 12}}>
-              Access denied. You need GOV ≥ {OPERATOR_THRESHOLD} to use Admin.
+              Access denied. You need GOV ≥ {operatorThreshold ?? '…'} to use Admin.
             </div>
           ) : null}
 
@@ -2,6 +2,9 @@ import type { NextApiRequest, NextApiResponse } from 'next'
 import { Connection, PublicKey } from '@solana/web3.js'
 import nacl from 'tweetnacl'
 import bs58 from 'bs58'
+import { BorshAccountsCoder } from '@coral-xyz/anchor'
+import { DEFAULT_OPERATOR_THRESHOLD_BPS, getOperatorThreshold } from '../../lib/operatorThreshold'
+import idl from '../../idl/roulette_table.json'
 
 type SocialLinks = {
   website?: string
@@ -10,8 +13,6 @@ type SocialLinks = {
   discord?: string
 }
 
-const OPERATOR_THRESHOLD = 51
-
 // In-memory store (good enough for dev/test; resets on restart/deploy)
 let currentLinks: SocialLinks = {
   website: process.env.NEXT_PUBLIC_SOCIAL_WEBSITE || '',
@@ -34,10 +35,39 @@ function isRecent(tsMs: number) {
   return Math.abs(now - tsMs) < 5 * 60_000 // 5 minutes window
 }
 
-async function hasGovBalance(connection: Connection, owner: PublicKey) {
+function firstTablePda(): PublicKey | null {
+  const first = (process.env.NEXT_PUBLIC_TABLE_PDA || '').split(/[\s,]+/g).map((s) => s.trim()).filter(Boolean)[0]
+  if (!first) return null
+  try {
+    return new PublicKey(first)
+  } catch {
+    return null
+  }
+}
+
+// GOV mint and threshold bps of the site's table; the default share of supply when none is set
+async function thresholdSource(connection: Connection): Promise<{ mint: PublicKey; bps: number } | null> {
+  const table = firstTablePda()
+  const programIdStr = process.env.NEXT_PUBLIC_PROGRAM_ID
+  if (table && programIdStr) {
+    const info = await connection.getAccountInfo(table, 'confirmed')
+    if (info?.data && info.owner.equals(new PublicKey(programIdStr))) {
+      const acc: any = new BorshAccountsCoder(idl as any).decode('Table', info.data)
+      return {
+        mint: new PublicKey(acc.gov_mint ?? acc.govMint),
+        bps: Number(acc.operator_threshold_bps ?? acc.operatorThresholdBps),
+      }
+    }
+  }
   const mintStr = process.env.NEXT_PUBLIC_GOV_MINT
-  if (!mintStr) return false
-  const mint = new PublicKey(mintStr)
+  if (!mintStr) return null
+  return { mint: new PublicKey(mintStr), bps: DEFAULT_OPERATOR_THRESHOLD_BPS }
+}
+
+async function hasGovBalance(connection: Connection, owner: PublicKey) {
+  const source = await thresholdSource(connection)
+  if (!source) return false
+  const { mint, bps } = source
   const ata = PublicKey.findProgramAddressSync(
     [owner.toBuffer(), new PublicKey('TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA').toBuffer(), mint.toBuffer()],
     new PublicKey('ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL')
@@ -46,7 +76,8 @@ async function hasGovBalance(connection: Connection, owner: PublicKey) {
   try {
     const bal = await connection.getTokenAccountBalance(ata, 'confirmed')
     const ui = Number(bal.value.uiAmount || 0)
-    return ui >= OPERATOR_THRESHOLD
+    const threshold = await getOperatorThreshold(connection, mint, bps)
+    return ui >= threshold
   } catch {
     return false
   }
@@ -130,9 +130,11 @@ export default function Home() {
   }
 
   // UI gating for liquidity controls: only show to table operator with enough GOV
-  const OPERATOR_THRESHOLD = 51
   const [isOperator, setIsOperator] = useState(false)
-  const [govBalanceUi, setGovBalanceUi] = useState(0)
+  // GOV needed to operate the selected table, and to create a new one
+  const [operatorThreshold, setOperatorThreshold] = useState<number | null>(null)
+  const [createThreshold, setCreateThreshold] = useState<number | null>(null)
+  const [govDepositedUi, setGovDepositedUi] = useState(0)
   const [govBalanceEnvUi, setGovBalanceEnvUi] = useState(0)
 
   const envGovMint = process.env.NEXT_PUBLIC_GOV_MINT
@@ -164,7 +166,7 @@ export default function Home() {
   // - If only NEXT_PUBLIC_TABLE_PDA is set => pinned (no switching)
   // - If NEXT_PUBLIC_TABLE_PDAS is set => show selector
   const tableAddressIsPinned = Boolean(envTablePda) && !hasEnvTableList
-  const isFunctionalTokenOwner = govBalanceEnvUi >= OPERATOR_THRESHOLD
+  const isFunctionalTokenOwner = createThreshold !== null && govBalanceEnvUi >= createThreshold
 
   // Read GOV balance directly from env mint (works even before a table exists)
   useEffect(() => {
@@ -172,10 +174,16 @@ export default function Home() {
       try {
         if (!publicKey || !envGovMint) {
           setGovBalanceEnvUi(0)
+          setCreateThreshold(null)
           return
         }
         const { provider } = await ensureProgram()
         const govMintPk = new PublicKey(envGovMint)
+        setCreateThreshold(await anchorLib.getOperatorThreshold(
+          provider.connection,
+          govMintPk,
+          anchorLib.DEFAULT_OPERATOR_THRESHOLD_BPS
+        ))
         const govAta = anchor.utils.token.associatedAddress({ mint: govMintPk, owner: publicKey })
         const bal = await provider.connection.getTokenAccountBalance(govAta, 'confirmed')
         setGovBalanceEnvUi(Number(bal.value.uiAmount || 0))
@@ -217,7 +225,8 @@ export default function Home() {
       try {
         if (!publicKey || !tableAddress) {
           setIsOperator(false)
-          setGovBalanceUi(0)
+          setGovDepositedUi(0)
+          setOperatorThreshold(null)
           return
         }
 
@@ -228,7 +237,8 @@ export default function Home() {
           tablePk = new PublicKey(tableAddress)
         } catch {
           setIsOperator(false)
-          setGovBalanceUi(0)
+          setGovDepositedUi(0)
+          setOperatorThreshold(null)
           return
         }
 
@@ -237,13 +247,20 @@ export default function Home() {
         setIsOperator(operatorPk.equals(publicKey))
 
         const govMintPk = new PublicKey(tableAcc.govMint)
-        const govAta = anchor.utils.token.associatedAddress({ mint: govMintPk, owner: publicKey })
-        const bal = await provider.connection.getTokenAccountBalance(govAta, 'confirmed')
-        setGovBalanceUi(Number(bal.value.uiAmount || 0))
+        setOperatorThreshold(await anchorLib.getOperatorThreshold(
+          provider.connection,
+          govMintPk,
+          Number(tableAcc.operatorThresholdBps)
+        ))
+        // The program checks the operator's deposited GOV, not their wallet balance
+        const govDepositPk = anchorLib.deriveGovDepositPda(program.programId, tablePk, publicKey)
+        const dep: any = await (program.account as any).govDeposit.fetch(govDepositPk).catch(() => null)
+        const { value: supply } = await provider.connection.getTokenSupply(govMintPk, 'confirmed')
+        setGovDepositedUi(Number(dep?.amount?.toString() || 0) / 10 ** supply.decimals)
       } catch {
-        // Table not created yet, ATA missing, etc.
+        // Table not created yet, no deposit, etc.
         setIsOperator(false)
-        setGovBalanceUi(0)
+        setGovDepositedUi(0)
       }
     })()
     // eslint-disable-next-line react-hooks/exhaustive-deps
@@ -252,7 +269,7 @@ export default function Home() {
   const handleCreateTable = useCallback(async () => {
     try {
       if (!isFunctionalTokenOwner) {
-        return alert('Only functional-token owners (GOV >= 51) can create tables')
+        return alert(`Only functional-token owners (GOV >= ${createThreshold ?? '?'}) can create tables`)
       }
       const { program, provider } = await ensureProgram()
       const seed = Math.floor(Math.random() * 1e6)
@@ -262,7 +279,7 @@ export default function Home() {
       setTableAddress(tablePda.toBase58())
       alert('createTable sent, table address set')
     } catch(e:any) { console.error(e); alert(e?.message||e) }
-  }, [publicKey, connection])
+  }, [publicKey, connection, isFunctionalTokenOwner, createThreshold])
 
   const [selectedNumber, setSelectedNumber] = useState<number | null>(null)
   const [selectedPair, setSelectedPair] = useState<[number, number] | null>(null)
@@ -838,7 +855,7 @@ export default function Home() {
             ) : null}
           </div>
 
-          {(isOperator && govBalanceUi >= OPERATOR_THRESHOLD) ? (
+          {(isOperator && operatorThreshold !== null && govDepositedUi >= operatorThreshold) ? (
             <div className="notice" style={{marginTop:16,background:'#fff3cd',borderColor:'rgba(0,0,0,0.08)'}}>
               <h3 style={{margin:'0 0 8px 0',fontSize:15}}>Deposit Liquidity (Required before bets)</h3>
               <div className="row">
@@ -2,7 +2,7 @@
 #![allow(unexpected_cfgs)]
 
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Burn, CloseAccount, Mint, MintTo, Token, TokenAccount, Transfer};
 use anchor_lang::solana_program::{program::invoke, system_instruction};
 use anchor_lang::solana_program::hash::hashv;
 
@@ -26,11 +26,36 @@ fn anchor_account_discriminator(account_name: &str) -> [u8; 8] {
     discriminator
 }
 
-pub const GOV_TOTAL_SUPPLY: u64 = 100;
-pub const OPERATOR_THRESHOLD: u64 = 51;
+// GOV fractions (operator threshold, quorum) are in basis points of the GOV mint's supply
+pub const BPS_DENOMINATOR: u64 = 10_000;
+// Defaults for new tables; operators can change them via `update_table_config`
 pub const BET_TIMEOUT_SECONDS: i64 = 1800; // 30 minutes
 pub const WITHDRAW_DELAY_SECONDS: i64 = 48 * 3600; // 48 hours default for PUBLIC
-pub const ROULETTE_MOD: u64 = 37;
+// Bounds on what `update_table_config` accepts for the two timeouts
+pub const MIN_BET_TIMEOUT_SECONDS: i64 = 60;
+pub const MAX_BET_TIMEOUT_SECONDS: i64 = 7 * 24 * 3600;
+pub const MIN_WITHDRAW_DELAY_SECONDS: i64 = 3600;
+pub const MAX_WITHDRAW_DELAY_SECONDS: i64 = 30 * 24 * 3600;
+// Pocket number of the American wheel's 00
+pub const DOUBLE_ZERO: u8 = 37;
+
+// Governance: votes are weighted by deposited GOV
+pub const PROPOSAL_VOTING_PERIOD_SECONDS: i64 = 3 * 24 * 3600;
+pub const PROPOSAL_EXECUTION_WINDOW_SECONDS: i64 = 7 * 24 * 3600;
+pub const PROPOSAL_QUORUM_BPS: u64 = 3_000;
+
+// Time the incumbent operator has to contest an operator claim
+pub const OPERATOR_CLAIM_CHALLENGE_SECONDS: i64 = 24 * 3600;
+
+// Cap on each of the protocol and operator fees, charged on player winnings
+pub const MAX_FEE_BPS: u16 = 1_000;
+
+// Precision of the per-GOV reward index
+pub const REWARD_INDEX_SCALE: u128 = 1_000_000_000_000;
+
+// GOV lockups: voting weight grows linearly with lock length, up to 2x at the maximum
+pub const MAX_GOV_LOCK_SECONDS: i64 = 365 * 24 * 3600;
+pub const MAX_LOCK_BOOST_BPS: u64 = 10_000;
 
 #[program]
 pub mod roulette_table {
@@ -40,18 +65,30 @@ pub mod roulette_table {
         ctx: Context<CreateTable>,
         seed: u64,
         mode: TableMode,
+        variant: TableVariant,
         min_bet: u64,
         max_bet: u64,
+        operator_threshold_bps: u16,
     ) -> Result<()> {
         require!(min_bet > 0, RouletteError::InvalidBetRange);
         require!(max_bet >= min_bet, RouletteError::InvalidBetRange);
+        // A strict majority of the supply, so at most one holder can ever qualify
+        require!(
+            (operator_threshold_bps as u64) > BPS_DENOMINATOR / 2
+                && (operator_threshold_bps as u64) <= BPS_DENOMINATOR,
+            RouletteError::InvalidOperatorThreshold
+        );
+        require!(ctx.accounts.gov_mint.supply > 0, RouletteError::InvalidOperatorThreshold);
 
         let table = &mut ctx.accounts.table;
         table.seed = seed;
         table.creator = ctx.accounts.creator.key();
         table.operator = ctx.accounts.creator.key();
         table.mode = mode;
+        table.variant = variant;
         table.paused = false;
+        table.retiring = false;
+        table.operator_threshold_bps = operator_threshold_bps;
 
         table.usdc_mint = ctx.accounts.usdc_mint.key();
         table.gov_mint = ctx.accounts.gov_mint.key();
@@ -60,19 +97,69 @@ pub mod roulette_table {
 
         table.min_bet = min_bet;
         table.max_bet = max_bet;
-
+        table.bet_timeout = BET_TIMEOUT_SECONDS;
+        table.withdraw_delay = WITHDRAW_DELAY_SECONDS;
+        table.enabled_kinds = BetKind::ALL;
+        table.profit_share_bps = 0;
+        table.operator_fee_bps = 0;
+        table.operator_fees = 0;
+        table.crank_tip = 0;
+        table.max_number_exposure = 0;
+        table.number_caps = NumberCaps::default();
+        table.max_payout_bps = 0;
+
+        table.exposure = Exposure::default();
         table.locked_liability = 0;
         table.active_bets = 0;
         table.bet_seq = 0;
 
+        table.liquidity = 0;
+        table.deposited_capital = 0;
+        table.realized_pnl = 0;
+
+        table.lp_mint = Pubkey::default();
+        table.total_shares = 0;
+        table.operator_shares = 0;
+
+        table.round_seq = 0;
+        table.proposal_seq = 0;
+
         table.withdraw_request_ts = 0;
         table.withdraw_request_amount = 0;
 
+        table.pending_operator = Pubkey::default();
+        table.pending_operator_gov = 0;
+        table.operator_claim_ts = 0;
+
+        table.total_gov_deposited = 0;
+        table.reward_index = 0;
+        table.reward_pool = 0;
+        table.rewards_owed = 0;
+        table.undistributed_pnl = 0;
+
+        table.pending_config = table.config();
+        table.config_request_ts = 0;
+        table.pending_mode = mode;
+        table.mode_request_ts = 0;
+        table.pending_number_caps = NumberCaps::default();
+        table.number_caps_request_ts = 0;
+
         table.bumps = TableBumps {
             table: ctx.bumps.table,
             control_vault_gov: ctx.bumps.control_vault_gov,
         };
 
+        emit_cpi!(TableCreated {
+            table: table.key(),
+            creator: table.creator,
+            global_state: table.global_state,
+            gov_mint: table.gov_mint,
+            mode,
+            variant,
+            min_bet,
+            max_bet,
+        });
+
         Ok(())
     }
 
@@ -82,6 +169,15 @@ pub mod roulette_table {
         gs.vault_usdc = ctx.accounts.global_vault_usdc.key();
         gs.total_locked_liability = 0;
         gs.total_active_bets = 0;
+        // Only the program's upgrade authority can get here, and it becomes the first admin
+        gs.admin = ctx.accounts.payer.key();
+        gs.pending_admin = Pubkey::default();
+        gs.pauser = Pubkey::default();
+        gs.fee_manager = Pubkey::default();
+        gs.paused = false;
+        gs.liquidity_floor = 0;
+        gs.protocol_fee_bps = 0;
+        gs.protocol_fees = 0;
         gs.bumps = GlobalBumps {
             global: ctx.bumps.global_state,
             vault_usdc: ctx.bumps.global_vault_usdc,
@@ -93,6 +189,9 @@ pub mod roulette_table {
     /// the current `GlobalState` struct (e.g. after a program upgrade / refactor).
     ///
     /// This keeps the PDA address stable and re-writes the account data with the current layout.
+    /// The old layout may not decode, so this is gated on the program's upgrade authority
+    /// (who becomes admin again) rather than on the stored admin. Whatever the old data still
+    /// decodes to (liability, roles, fees, breaker) is carried over; only missing fields are reset.
     pub fn repair_global(ctx: Context<RepairGlobal>) -> Result<()> {
         // Must already be program-owned to be repairable.
         require_keys_eq!(
@@ -101,6 +200,8 @@ pub mod roulette_table {
             RouletteError::InvalidGlobalStateOwner
         );
 
+        let previous = decode_previous_global(&ctx.accounts.global_state.try_borrow_data()?);
+
         let desired_len: usize = 8 + GlobalState::SIZE;
 
         // Ensure rent-exempt balance for desired length.
@@ -139,22 +240,144 @@ pub mod roulette_table {
         let gs = GlobalState {
             usdc_mint: ctx.accounts.usdc_mint.key(),
             vault_usdc: ctx.accounts.global_vault_usdc.key(),
-            total_locked_liability: 0,
-            total_active_bets: 0,
+            admin: ctx.accounts.payer.key(),
+            pending_admin: Pubkey::default(),
             bumps: GlobalBumps {
                 global: ctx.bumps.global_state,
                 vault_usdc: ctx.bumps.global_vault_usdc,
             },
+            ..previous
         };
         gs.try_serialize(&mut &mut data[8..])?;
 
         Ok(())
     }
 
+    // --- global authority ---
+
+    /// First step of an admin handover: nominates `new_admin`, who must accept.
+    pub fn transfer_admin(ctx: Context<GlobalAdmin>, new_admin: Pubkey) -> Result<()> {
+        let gs = &mut ctx.accounts.global_state;
+        gs.pending_admin = new_admin;
+        emit_cpi!(AdminTransferStarted {
+            global_state: gs.key(),
+            admin: gs.admin,
+            pending_admin: new_admin,
+        });
+        Ok(())
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        let gs = &mut ctx.accounts.global_state;
+        let previous = gs.admin;
+        gs.admin = ctx.accounts.pending_admin.key();
+        gs.pending_admin = Pubkey::default();
+        emit_cpi!(AdminTransferred {
+            global_state: gs.key(),
+            previous,
+            admin: gs.admin,
+        });
+        Ok(())
+    }
+
+    /// Assigns a global role. `Pubkey::default()` revokes it, leaving it to the admin alone.
+    pub fn set_role(ctx: Context<GlobalAdmin>, role: GlobalRole, authority: Pubkey) -> Result<()> {
+        let gs = &mut ctx.accounts.global_state;
+        match role {
+            GlobalRole::Pauser => gs.pauser = authority,
+            GlobalRole::FeeManager => gs.fee_manager = authority,
+        }
+        emit_cpi!(RoleSet {
+            global_state: gs.key(),
+            role,
+            authority,
+        });
+        Ok(())
+    }
+
+    // --- global circuit breaker ---
+
+    /// Stops (or resumes) new bets on every table of this mint. Pending bets can still be
+    /// resolved or refunded while paused.
+    pub fn set_global_pause(ctx: Context<GlobalPauser>, paused: bool) -> Result<()> {
+        let gs = &mut ctx.accounts.global_state;
+        gs.paused = paused;
+        emit_cpi!(GlobalPaused {
+            global_state: gs.key(),
+            paused,
+            breaker_tripped: false,
+        });
+        Ok(())
+    }
+
+    /// Sets the free-liquidity floor (vault balance minus `total_locked_liability`) under which
+    /// the breaker trips automatically. Zero disables it.
+    pub fn set_liquidity_floor(ctx: Context<GlobalAdmin>, floor: u64) -> Result<()> {
+        let gs = &mut ctx.accounts.global_state;
+        gs.liquidity_floor = floor;
+        emit_cpi!(LiquidityFloorSet {
+            global_state: gs.key(),
+            floor,
+        });
+        Ok(())
+    }
+
+    /// Sets the protocol fee taken from player winnings on every table of this mint.
+    pub fn set_protocol_fee(ctx: Context<GlobalFeeManager>, fee_bps: u16) -> Result<()> {
+        require!(fee_bps <= MAX_FEE_BPS, RouletteError::FeeTooHigh);
+        let gs = &mut ctx.accounts.global_state;
+        gs.protocol_fee_bps = fee_bps;
+        emit_cpi!(ProtocolFeeSet {
+            global_state: gs.key(),
+            fee_bps,
+        });
+        Ok(())
+    }
+
+    /// Pays out the accrued protocol fee ledger.
+    pub fn claim_protocol_fees(ctx: Context<ClaimProtocolFees>) -> Result<()> {
+        let gs = &mut ctx.accounts.global_state;
+        let amount = gs.protocol_fees;
+        require!(amount > 0, RouletteError::NothingToClaim);
+        gs.protocol_fees = 0;
+
+        transfer_from_vault(
+            gs,
+            &ctx.accounts.global_vault_usdc,
+            &ctx.accounts.recipient_usdc_ata,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+
+        emit_cpi!(FeesClaimed {
+            global_state: gs.key(),
+            table: Pubkey::default(),
+            recipient: ctx.accounts.recipient_usdc_ata.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    /// Permissionless: trips the breaker when free vault liquidity is below the floor,
+    /// e.g. after withdrawals drained the vault outside of bet settlement.
+    pub fn trip_circuit_breaker(ctx: Context<TripCircuitBreaker>) -> Result<()> {
+        let vault_balance = ctx.accounts.global_vault_usdc.amount;
+        let gs = &mut ctx.accounts.global_state;
+        require!(below_liquidity_floor(gs, vault_balance), RouletteError::AboveLiquidityFloor);
+        gs.paused = true;
+        emit_cpi!(GlobalPaused {
+            global_state: gs.key(),
+            paused: true,
+            breaker_tripped: true,
+        });
+        Ok(())
+    }
+
     // --- GOV / management ---
 
     pub fn deposit_gov(ctx: Context<DepositGov>, amount: u64) -> Result<()> {
         require!(amount > 0, RouletteError::InvalidAmount);
+        require!(!ctx.accounts.table.retiring, RouletteError::TableRetiring);
 
         // transfer GOV -> control_vault
         let cpi_accounts = Transfer {
@@ -166,27 +389,50 @@ pub mod roulette_table {
         token::transfer(cpi_ctx, amount)?;
 
         // update deposit record
+        let table = &mut ctx.accounts.table;
         let dep = &mut ctx.accounts.gov_deposit;
-        dep.table = ctx.accounts.table.key();
+        dep.table = table.key();
         dep.depositor = ctx.accounts.depositor.key();
+        // Rewards earned so far are booked at the old amount; a fresh record starts at the index
+        sync_rewards(table, dep)?;
         dep.amount = dep.amount.saturating_add(amount);
+        table.total_gov_deposited = table.total_gov_deposited.saturating_add(amount);
+
+        emit_cpi!(GovDeposited {
+            table: dep.table,
+            depositor: dep.depositor,
+            amount,
+            total: dep.amount,
+        });
 
         Ok(())
     }
 
-    pub fn withdraw_gov(ctx: Context<WithdrawGov>, amount: u64) -> Result<()> {
+    /// Withdraws deposited GOV. The operator passes deposits delegated to them as remaining
+    /// accounts, since their control power must stay at the threshold.
+    pub fn withdraw_gov<'info>(ctx: Context<'_, '_, 'info, 'info, WithdrawGov<'info>>, amount: u64) -> Result<()> {
         require!(amount > 0, RouletteError::InvalidAmount);
 
-        let table = &ctx.accounts.table;
-        let dep = &mut ctx.accounts.gov_deposit;
+        let table = &mut ctx.accounts.table;
+        let now = Clock::get()?.unix_timestamp;
 
-        if table.operator == dep.depositor {
-            let remaining = dep.amount.saturating_sub(amount);
-            require!(remaining >= OPERATOR_THRESHOLD, RouletteError::OperatorCantDropBelowThreshold);
+        // GOV backing the operator may only leave while the operator keeps the threshold.
+        // A retiring table no longer needs an operator, so its GOV can be fully recovered.
+        if backs_operator(table, &ctx.accounts.gov_deposit) && !table.retiring {
+            let remaining =
+                operator_power_without(table, &ctx.accounts.gov_deposit, amount, ctx.remaining_accounts)?;
+            let threshold = operator_threshold(table, ctx.accounts.gov_mint.supply)?;
+            require!(remaining >= threshold, RouletteError::OperatorCantDropBelowThreshold);
         }
 
+        let dep = &mut ctx.accounts.gov_deposit;
         require!(dep.amount >= amount, RouletteError::InsufficientGovDeposit);
+        require!(now >= dep.lock_until, RouletteError::GovLocked);
+        // GOV that voted stays deposited until the vote ends, so it cannot vote twice
+        require!(now >= dep.vote_lock_until, RouletteError::GovLockedByVote);
+        sync_rewards(table, dep)?;
         dep.amount -= amount;
+        table.total_gov_deposited = table.total_gov_deposited.saturating_sub(amount);
 
         // PDA signer: control_vault_gov owner = table PDA
         let seed_bytes = table.seed.to_le_bytes();
@@ -201,268 +447,1457 @@ pub mod roulette_table {
             CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
         token::transfer(cpi_ctx, amount)?;
 
+        emit_cpi!(GovWithdrawn {
+            table: dep.table,
+            depositor: dep.depositor,
+            amount,
+            total: dep.amount,
+        });
+
         Ok(())
     }
 
-    pub fn claim_operator(ctx: Context<ClaimOperator>) -> Result<()> {
+    /// Pays the depositor's share of distributed house profit in USDC.
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
         let table = &mut ctx.accounts.table;
-        let dep = &ctx.accounts.gov_deposit;
-
-        require!(dep.amount >= OPERATOR_THRESHOLD, RouletteError::NotEnoughGovToOperate);
-        table.operator = dep.depositor;
-
+        let dep = &mut ctx.accounts.gov_deposit;
+        sync_rewards(table, dep)?;
+
+        let amount = dep.pending_rewards;
+        require!(amount > 0, RouletteError::NothingToClaim);
+        dep.pending_rewards = 0;
+        table.reward_pool = table.reward_pool.saturating_sub(amount);
+        table.rewards_owed = table.rewards_owed.saturating_sub(amount);
+
+        transfer_from_vault(
+            &ctx.accounts.global_state,
+            &ctx.accounts.global_vault_usdc,
+            &ctx.accounts.depositor_usdc_ata,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+
+        emit_cpi!(RewardsClaimed {
+            table: table.key(),
+            depositor: dep.depositor,
+            amount,
+        });
         Ok(())
     }
 
-    pub fn set_mode(ctx: Context<OnlyOperator>, mode: TableMode) -> Result<()> {
-        let table = &mut ctx.accounts.table;
-        table.mode = mode;
+    /// Locks the whole deposit for `duration` seconds from now (never shortening an existing
+    /// lock) in exchange for boosted voting weight while the lock lasts.
+    pub fn lock_gov(ctx: Context<ManageGovDeposit>, duration: i64) -> Result<()> {
+        require!(
+            duration > 0 && duration <= MAX_GOV_LOCK_SECONDS,
+            RouletteError::InvalidLockDuration
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let dep = &mut ctx.accounts.gov_deposit;
+        require!(dep.amount > 0, RouletteError::InsufficientGovDeposit);
+
+        dep.lock_until = dep.lock_until.max(now.saturating_add(duration));
+        dep.lock_boost_bps = lock_boost_bps(dep.lock_until.saturating_sub(now));
+
+        emit_cpi!(GovLocked {
+            table: dep.table,
+            depositor: dep.depositor,
+            lock_until: dep.lock_until,
+            boost_bps: dep.lock_boost_bps,
+        });
         Ok(())
     }
 
-    pub fn pause(ctx: Context<OnlyOperator>) -> Result<()> {
-        let table = &mut ctx.accounts.table;
-        table.paused = true;
+    /// Hands the deposit's control and voting power to `delegate` without moving tokens.
+    /// Delegating to oneself (or the default key) takes the power back. Power backing the
+    /// operator only moves away while the operator keeps the threshold without it; the other
+    /// deposits backing them are passed as remaining accounts.
+    pub fn delegate_gov<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DelegateGov<'info>>,
+        delegate: Pubkey,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let table = &ctx.accounts.table;
+        let dep = &ctx.accounts.gov_deposit;
+        // Power counted in a running vote cannot move to another voter
+        require!(now >= dep.vote_lock_until, RouletteError::GovLockedByVote);
+
+        let delegate = if delegate == dep.depositor { Pubkey::default() } else { delegate };
+        let still_backs = delegate == table.operator
+            || (delegate == Pubkey::default() && dep.depositor == table.operator);
+        if backs_operator(table, dep) && !still_backs && !table.retiring {
+            let remaining = operator_power_without(table, dep, dep.amount, ctx.remaining_accounts)?;
+            let threshold = operator_threshold(table, ctx.accounts.gov_mint.supply)?;
+            require!(remaining >= threshold, RouletteError::OperatorCantDropBelowThreshold);
+        }
+
+        let dep = &mut ctx.accounts.gov_deposit;
+        dep.delegate = delegate;
+
+        emit_cpi!(GovDelegated {
+            table: dep.table,
+            depositor: dep.depositor,
+            delegate: dep.delegate,
+        });
         Ok(())
     }
 
-    pub fn unpause(ctx: Context<OnlyOperator>) -> Result<()> {
-        let table = &mut ctx.accounts.table;
-        table.paused = false;
+    /// Closes an emptied GOV deposit record and returns its rent to the depositor. Works after
+    /// the table itself has been closed.
+    pub fn close_gov_deposit(ctx: Context<CloseGovDeposit>) -> Result<()> {
+        emit_cpi!(GovDepositClosed {
+            table: ctx.accounts.gov_deposit.table,
+            depositor: ctx.accounts.depositor.key(),
+        });
         Ok(())
     }
 
-    // --- liquidity ---
-
-    pub fn deposit_liquidity_usdc(ctx: Context<DepositLiquidityUsdc>, amount: u64) -> Result<()> {
-        require!(amount > 0, RouletteError::InvalidAmount);
+    /// Starts an operator handover to the signing claimant. The claim only takes effect via
+    /// `finalize_operator_claim` once the challenge window has passed uncontested. A pending
+    /// claim can be replaced by one backed by more GOV. Deposits delegated to the claimant
+    /// are passed as remaining accounts.
+    pub fn claim_operator<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimOperator<'info>>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let claimant = ctx.accounts.claimant.key();
+        let table = &mut ctx.accounts.table;
+        let delegated = delegated_deposits(table.key(), claimant, ctx.remaining_accounts)?;
+        let power = holder_power(&ctx.accounts.gov_deposit, &claimant, &delegated, now)?.control;
 
-        // Only the table operator with enough GOV tokens can manage liquidity
+        let threshold = operator_threshold(table, ctx.accounts.gov_mint.supply)?;
+        require!(power >= threshold, RouletteError::NotEnoughGovToOperate);
+        require!(table.operator != claimant, RouletteError::AlreadyOperator);
+        require_operator_change_allowed(table, now)?;
         require!(
-            ctx.accounts.operator_gov_ata.amount >= OPERATOR_THRESHOLD,
-            RouletteError::NotEnoughGovToOperate
+            table.pending_operator == Pubkey::default() || power > table.pending_operator_gov,
+            RouletteError::OperatorClaimPending
         );
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.operator_usdc_ata.to_account_info(),
-            to: ctx.accounts.global_vault_usdc.to_account_info(),
-            authority: ctx.accounts.operator.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
-        token::transfer(cpi_ctx, amount)?;
+        table.pending_operator = claimant;
+        table.pending_operator_gov = power;
+        table.operator_claim_ts = now;
+
+        emit_cpi!(OperatorClaimStarted {
+            table: table.key(),
+            claimant,
+            gov_amount: power,
+            challenge_ends_ts: now.saturating_add(OPERATOR_CLAIM_CHALLENGE_SECONDS),
+        });
 
         Ok(())
     }
 
-    pub fn request_withdraw(ctx: Context<OnlyOperator>, amount: u64) -> Result<()> {
+    /// Lets the incumbent cancel a pending claim during the challenge window by showing they
+    /// still hold the operator threshold (own plus delegated GOV, as remaining accounts).
+    /// Claims therefore only succeed against an operator who has lost their backing.
+    pub fn challenge_operator_claim<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ChallengeOperatorClaim<'info>>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let operator = ctx.accounts.operator.key();
         let table = &mut ctx.accounts.table;
-        require!(table.mode == TableMode::Public, RouletteError::NotInPublicMode);
-        require!(amount > 0, RouletteError::InvalidAmount);
+        require!(table.pending_operator != Pubkey::default(), RouletteError::NoOperatorClaim);
+        require!(
+            now.saturating_sub(table.operator_claim_ts) < OPERATOR_CLAIM_CHALLENGE_SECONDS,
+            RouletteError::ChallengeWindowClosed
+        );
 
-        require!(table.active_bets == 0, RouletteError::ActiveBetsExist);
-        require!(table.locked_liability == 0, RouletteError::LiabilityLocked);
+        let delegated = delegated_deposits(table.key(), operator, ctx.remaining_accounts)?;
+        let power = holder_power(&ctx.accounts.gov_deposit, &operator, &delegated, now)?.control;
+        let threshold = operator_threshold(table, ctx.accounts.gov_mint.supply)?;
+        require!(power >= threshold, RouletteError::ChallengeTooSmall);
+
+        emit_cpi!(OperatorClaimChallenged {
+            table: table.key(),
+            claimant: table.pending_operator,
+            operator,
+            operator_gov: power,
+        });
+
+        table.pending_operator = Pubkey::default();
+        table.pending_operator_gov = 0;
+        table.operator_claim_ts = 0;
 
-        table.withdraw_request_amount = amount;
-        table.withdraw_request_ts = Clock::get()?.unix_timestamp;
         Ok(())
     }
 
-    pub fn execute_withdraw(ctx: Context<WithdrawLiquidityUsdc>, amount: u64) -> Result<()> {
+    /// Completes an uncontested claim; the claimant must sign and still hold the threshold.
+    /// The outgoing operator's capital is paid out to them rather than passed on.
+    pub fn finalize_operator_claim<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeOperatorClaim<'info>>,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let claimant = ctx.accounts.claimant.key();
         let table = &mut ctx.accounts.table;
-        require!(amount > 0, RouletteError::InvalidAmount);
-
-        // Only the table operator with enough GOV tokens can manage liquidity
         require!(
-            ctx.accounts.operator_gov_ata.amount >= OPERATOR_THRESHOLD,
-            RouletteError::NotEnoughGovToOperate
+            now.saturating_sub(table.operator_claim_ts) >= OPERATOR_CLAIM_CHALLENGE_SECONDS,
+            RouletteError::ChallengeWindowOpen
         );
 
-        require!(table.active_bets == 0, RouletteError::ActiveBetsExist);
-        require!(table.locked_liability == 0, RouletteError::LiabilityLocked);
-
-        if table.mode == TableMode::Public {
-            require!(table.withdraw_request_amount == amount, RouletteError::WithdrawRequestMismatch);
-            let now = Clock::get()?.unix_timestamp;
-            require!(
-                now.saturating_sub(table.withdraw_request_ts) >= WITHDRAW_DELAY_SECONDS,
-                RouletteError::WithdrawDelayNotPassed
-            );
-            table.withdraw_request_amount = 0;
-            table.withdraw_request_ts = 0;
+        let delegated = delegated_deposits(table.key(), claimant, ctx.remaining_accounts)?;
+        let power = holder_power(&ctx.accounts.gov_deposit, &claimant, &delegated, now)?.control;
+        let threshold = operator_threshold(table, ctx.accounts.gov_mint.supply)?;
+        require!(power >= threshold, RouletteError::NotEnoughGovToOperate);
+        require_operator_change_allowed(table, now)?;
+
+        let previous = table.operator;
+        let shares = table.operator_shares;
+        let amount = settle_operator_position(
+            table,
+            &ctx.accounts.global_state,
+            &ctx.accounts.global_vault_usdc,
+            &ctx.accounts.previous_operator_usdc_ata,
+            &ctx.accounts.token_program,
+        )?;
+        if shares > 0 {
+            emit_cpi!(OperatorPositionSettled {
+                table: table.key(),
+                operator: previous,
+                shares,
+                amount,
+            });
         }
 
-        // Withdraw from shared global vault (signed by GlobalState PDA)
-        let gs = &mut ctx.accounts.global_state;
-        let signer_seeds: &[&[&[u8]]] = &[&[b"global", gs.usdc_mint.as_ref(), &[gs.bumps.global]]];
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.global_vault_usdc.to_account_info(),
-            to: ctx.accounts.operator_usdc_ata.to_account_info(),
-            authority: gs.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
-        token::transfer(cpi_ctx, amount)?;
+        table.operator = claimant;
+        table.pending_operator = Pubkey::default();
+        table.pending_operator_gov = 0;
+        table.operator_claim_ts = 0;
+        table.withdraw_request_ts = 0;
+        table.withdraw_request_amount = 0;
+
+        emit_cpi!(OperatorClaimed {
+            table: table.key(),
+            previous,
+            operator: table.operator,
+            gov_amount: power,
+        });
 
         Ok(())
     }
 
-    // --- game (USDC + ORAO VRF) ---
+    /// Leaving Public mode lifts the timelocks players and LPs rely on, so that switch is
+    /// queued for the withdraw delay and applied by `apply_mode`. Switching to Public applies
+    /// at once and discards a queued switch.
+    pub fn set_mode(ctx: Context<OnlyOperator>, mode: TableMode) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let table = &mut ctx.accounts.table;
+        match change_mode(table, mode, now) {
+            Some(eta) => emit_cpi!(ModeChangeQueued {
+                table: table.key(),
+                mode,
+                eta,
+            }),
+            None => emit_cpi!(ModeChanged {
+                table: table.key(),
+                mode,
+            }),
+        }
+        Ok(())
+    }
 
-    pub fn place_bet(ctx: Context<PlaceBet>, bet: BetKind, stake: u64, force: [u8; 32]) -> Result<()> {
+    /// Applies a queued mode switch once its timelock has passed.
+    pub fn apply_mode(ctx: Context<OnlyOperator>) -> Result<()> {
         let table = &mut ctx.accounts.table;
+        require!(table.mode_request_ts != 0, RouletteError::NoPendingModeChange);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now.saturating_sub(table.mode_request_ts) >= table.withdraw_delay,
+            RouletteError::ModeTimelockActive
+        );
 
-        require!(!table.paused, RouletteError::Paused);
-        require!(stake >= table.min_bet && stake <= table.max_bet, RouletteError::InvalidStake);
+        let mode = table.pending_mode;
+        table.mode = mode;
+        table.mode_request_ts = 0;
+        emit_cpi!(ModeChanged {
+            table: table.key(),
+            mode,
+        });
+        Ok(())
+    }
 
-        validate_bet_kind(&bet)?;
+    pub fn pause(ctx: Context<OnlyOperator>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        set_paused(table, true);
+        emit_cpi!(Paused {
+            table: table.key(),
+            paused: true,
+        });
+        Ok(())
+    }
 
-        let multiplier = bet_multiplier(&bet);
-        let max_total_payout = stake
-            .checked_mul((multiplier + 1) as u64)
-            .ok_or(RouletteError::MathOverflow)?;
+    pub fn unpause(ctx: Context<OnlyOperator>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        set_paused(table, false);
+        emit_cpi!(Paused {
+            table: table.key(),
+            paused: false,
+        });
+        Ok(())
+    }
 
-        let gs = &mut ctx.accounts.global_state;
+    /// Changes limits, timeouts and enabled bet kinds. In Public mode a change that is worse
+    /// for players is queued and only takes effect via `apply_table_config` after the table's
+    /// withdraw delay; any other change applies at once and discards a queued one.
+    pub fn update_table_config(ctx: Context<OnlyOperator>, config: TableConfig) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let table = &mut ctx.accounts.table;
+        match change_config(table, config, now)? {
+            Some(eta) => emit_cpi!(TableConfigQueued {
+                table: table.key(),
+                config,
+                eta,
+            }),
+            None => emit_cpi!(TableConfigUpdated {
+                table: table.key(),
+                config,
+            }),
+        }
+        Ok(())
+    }
 
-        let vault_balance = ctx.accounts.global_vault_usdc.amount;
-        let available = vault_balance.saturating_sub(gs.total_locked_liability);
-        require!(available >= max_total_payout, RouletteError::InsufficientLiquidity);
+    /// Applies a queued config change once its timelock has passed.
+    pub fn apply_table_config(ctx: Context<OnlyOperator>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        require!(table.config_request_ts != 0, RouletteError::NoPendingConfig);
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now.saturating_sub(table.config_request_ts) >= table.withdraw_delay,
+            RouletteError::ConfigTimelockActive
+        );
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.player_usdc_ata.to_account_info(),
-            to: ctx.accounts.global_vault_usdc.to_account_info(),
-            authority: ctx.accounts.player.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
-        token::transfer(cpi_ctx, stake)?;
+        let config = table.pending_config;
+        table.apply_config(&config);
+        table.config_request_ts = 0;
+        emit_cpi!(TableConfigUpdated {
+            table: table.key(),
+            config,
+        });
+        Ok(())
+    }
 
-        table.locked_liability = table.locked_liability.saturating_add(max_total_payout);
-        table.active_bets = table.active_bets.saturating_add(1);
-        table.bet_seq = table.bet_seq.saturating_add(1);
+    /// Sets per-number exposure caps on top of the table-wide `max_number_exposure`. Like
+    /// config changes, tightening a cap in Public mode is queued for the withdraw delay and
+    /// applied by `apply_number_caps`; loosening applies at once and discards a queued change.
+    pub fn set_number_caps(ctx: Context<OnlyOperator>, caps: NumberCaps) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        if table.mode == TableMode::Public && number_caps_tightened(&table.number_caps, &caps) {
+            let now = Clock::get()?.unix_timestamp;
+            table.pending_number_caps = caps;
+            table.number_caps_request_ts = now;
+            emit_cpi!(NumberCapsQueued {
+                table: table.key(),
+                caps,
+                eta: now.saturating_add(table.withdraw_delay),
+            });
+            return Ok(());
+        }
 
-        gs.total_locked_liability = gs.total_locked_liability.saturating_add(max_total_payout);
-        gs.total_active_bets = gs.total_active_bets.saturating_add(1);
+        table.number_caps = caps;
+        table.number_caps_request_ts = 0;
+        emit_cpi!(NumberCapsUpdated {
+            table: table.key(),
+            caps,
+        });
+        Ok(())
+    }
 
+    /// Applies queued per-number caps once their timelock has passed.
+    pub fn apply_number_caps(ctx: Context<OnlyOperator>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        require!(table.number_caps_request_ts != 0, RouletteError::NoPendingConfig);
         let now = Clock::get()?.unix_timestamp;
-        let bet_acc = &mut ctx.accounts.bet;
-        bet_acc.table = table.key();
-        bet_acc.player = ctx.accounts.player.key();
-        bet_acc.stake = stake;
-        bet_acc.multiplier = multiplier;
-        bet_acc.max_total_payout = max_total_payout;
-        bet_acc.kind = bet;
-        bet_acc.state = BetState::Pending;
-        bet_acc.created_ts = now;
-        bet_acc.force = force;
-        bet_acc.randomness_account = ctx.accounts.random.key();
-
-        // ORAO VRF CPI: request randomness
-        let cpi_program = ctx.accounts.vrf.to_account_info();
-        let cpi_accounts = orao_cpi::accounts::RequestV2 {
-            payer: ctx.accounts.player.to_account_info(),
-            network_state: ctx.accounts.config.clone(),
-            treasury: ctx.accounts.treasury.clone(),
-            request: ctx.accounts.random.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
-        orao_cpi::request_v2(cpi_ctx, force)?;
+        require!(
+            now.saturating_sub(table.number_caps_request_ts) >= table.withdraw_delay,
+            RouletteError::ConfigTimelockActive
+        );
 
+        let caps = table.pending_number_caps;
+        table.number_caps = caps;
+        table.number_caps_request_ts = 0;
+        emit_cpi!(NumberCapsUpdated {
+            table: table.key(),
+            caps,
+        });
         Ok(())
     }
 
-    pub fn resolve_bet(ctx: Context<ResolveBet>) -> Result<()> {
+    // --- governance ---
+
+    /// Opens a proposal for GOV depositors to vote on. Voting lasts at least the table's
+    /// withdraw delay, which is the notice period players get for the executed action.
+    pub fn create_proposal(ctx: Context<CreateProposal>, action: ProposalAction) -> Result<()> {
+        require!(ctx.accounts.gov_deposit.amount > 0, RouletteError::InsufficientGovDeposit);
+        match &action {
+            ProposalAction::SetConfig { config } => validate_table_config(config)?,
+            ProposalAction::WithdrawLiquidity { amount } => {
+                require!(*amount > 0, RouletteError::InvalidAmount)
+            }
+            _ => {}
+        }
+
+        let now = Clock::get()?.unix_timestamp;
         let table = &mut ctx.accounts.table;
-        let bet = &mut ctx.accounts.bet;
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.table = table.key();
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.id = table.proposal_seq;
+        proposal.action = action.clone();
+        proposal.created_ts = now;
+        proposal.voting_ends_ts = now.saturating_add(PROPOSAL_VOTING_PERIOD_SECONDS.max(table.withdraw_delay));
+        proposal.quorum = bps_of(ctx.accounts.gov_mint.supply, PROPOSAL_QUORUM_BPS)?;
+        proposal.yes_votes = 0;
+        proposal.no_votes = 0;
+        proposal.turnout = 0;
+        proposal.executed = false;
+
+        table.proposal_seq = table.proposal_seq.saturating_add(1);
+
+        emit_cpi!(ProposalCreated {
+            table: proposal.table,
+            proposal: proposal.key(),
+            proposer: proposal.proposer,
+            id: proposal.id,
+            action,
+            voting_ends_ts: proposal.voting_ends_ts,
+        });
+        Ok(())
+    }
 
-        require!(bet.state == BetState::Pending, RouletteError::BetNotPending);
-        // Read ORAO randomness account and ensure it's fulfilled
-        let mut data: &[u8] = &ctx.accounts.random.data.borrow();
-        let randomness_state = RandomnessAccountData::try_deserialize_unchecked(&mut data)
-            .map_err(|_| RouletteError::RandomnessDecodeFailed)?;
-        let fulfilled = randomness_state.fulfilled_randomness();
-        require!(fulfilled.is_some(), RouletteError::RandomnessNotFulfilled);
-        let rnd = fulfilled.unwrap();
-
-        let n = roulette_number_from_randomness(&rnd);
-
-        let won = bet_covers_number(&bet.kind, n);
-        let total_payout = if won {
-            bet.stake
-                .checked_mul((bet.multiplier + 1) as u64)
-                .ok_or(RouletteError::MathOverflow)?
-        } else {
-            0
-        };
+    /// Votes with the caller's GOV power: their own deposit unless delegated away, plus
+    /// deposits delegated to them passed as writable remaining accounts. Every deposit counted stays
+    /// locked until voting ends.
+    pub fn vote<'info>(ctx: Context<'_, '_, 'info, 'info, Vote<'info>>, support: bool) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let voter = ctx.accounts.voter.key();
+        let proposal = &mut ctx.accounts.proposal;
+        require!(now < proposal.voting_ends_ts, RouletteError::VotingClosed);
 
+        let mut delegated = delegated_deposits(proposal.table, voter, ctx.remaining_accounts)?;
+        let dep = &mut ctx.accounts.gov_deposit;
+        let power = holder_power(dep, &voter, &delegated, now)?;
+        let weight = power.voting;
+        require!(weight > 0, RouletteError::InsufficientGovDeposit);
+
+        dep.vote_lock_until = dep.vote_lock_until.max(proposal.voting_ends_ts);
+        for d in delegated.iter_mut() {
+            d.vote_lock_until = d.vote_lock_until.max(proposal.voting_ends_ts);
+            d.exit(&crate::ID)?;
+        }
 
-        if total_payout > 0 {
-            // Sign as GlobalState PDA to move funds from the global vault
-            let gs = &mut ctx.accounts.global_state;
-            let signer_seeds: &[&[&[u8]]] = &[&[b"global", gs.usdc_mint.as_ref(), &[gs.bumps.global]]];
-            let cpi_accounts = Transfer {
-                from: ctx.accounts.global_vault_usdc.to_account_info(),
-                to: ctx.accounts.player_usdc_ata.to_account_info(),
-                authority: gs.to_account_info(),
-            };
-            let cpi_ctx = CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                cpi_accounts,
-                signer_seeds,
-            );
-            token::transfer(cpi_ctx, total_payout)?;
+        if support {
+            proposal.yes_votes = proposal.yes_votes.saturating_add(weight);
+        } else {
+            proposal.no_votes = proposal.no_votes.saturating_add(weight);
         }
+        proposal.turnout = proposal.turnout.saturating_add(power.control);
+
+        let record = &mut ctx.accounts.vote_record;
+        record.proposal = proposal.key();
+        record.voter = ctx.accounts.voter.key();
+        record.weight = weight;
+        record.support = support;
+
+        emit_cpi!(VoteCast {
+            proposal: record.proposal,
+            voter: record.voter,
+            support,
+            weight,
+        });
+        Ok(())
+    }
 
-        table.locked_liability = table.locked_liability.saturating_sub(bet.max_total_payout);
-        table.active_bets = table.active_bets.saturating_sub(1);
-        let gs = &mut ctx.accounts.global_state;
-        gs.total_locked_liability = gs.total_locked_liability.saturating_sub(bet.max_total_payout);
-        gs.total_active_bets = gs.total_active_bets.saturating_sub(1);
+    /// Executes a passed proposal after voting ends. Permissionless; the liquidity accounts
+    /// are only needed for `WithdrawLiquidity`, which pays the table operator. Actions run
+    /// through the same helpers as the operator's instructions, timelocks included.
+    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let proposal = &mut ctx.accounts.proposal;
+        require!(!proposal.executed, RouletteError::ProposalAlreadyExecuted);
+        require!(now >= proposal.voting_ends_ts, RouletteError::VotingStillOpen);
+        require!(
+            now.saturating_sub(proposal.voting_ends_ts) <= PROPOSAL_EXECUTION_WINDOW_SECONDS,
+            RouletteError::ProposalExpired
+        );
+        require!(proposal_passed(proposal), RouletteError::ProposalNotPassed);
+        proposal.executed = true;
 
-        bet.state = BetState::Resolved;
-        bet.result_number = Some(n);
+        let table = &mut ctx.accounts.table;
+        match proposal.action.clone() {
+            ProposalAction::SetOperator { operator } => {
+                require_operator_change_allowed(table, now)?;
+                let shares = table.operator_shares;
+                if operator != table.operator && shares > 0 {
+                    let (Some(global_state), Some(global_vault_usdc), Some(operator_usdc_ata), Some(token_program)) = (
+                        ctx.accounts.global_state.as_ref(),
+                        ctx.accounts.global_vault_usdc.as_ref(),
+                        ctx.accounts.operator_usdc_ata.as_ref(),
+                        ctx.accounts.token_program.as_ref(),
+                    ) else {
+                        return err!(RouletteError::MissingProposalAccounts);
+                    };
+                    require_keys_eq!(
+                        global_vault_usdc.key(),
+                        global_state.vault_usdc,
+                        RouletteError::VaultMismatch
+                    );
+                    let amount =
+                        settle_operator_position(table, global_state, global_vault_usdc, operator_usdc_ata, token_program)?;
+                    emit_cpi!(OperatorPositionSettled {
+                        table: table.key(),
+                        operator: table.operator,
+                        shares,
+                        amount,
+                    });
+                }
+                table.operator = operator;
+                // The vote supersedes any claim still in its challenge window
+                table.pending_operator = Pubkey::default();
+                table.pending_operator_gov = 0;
+                table.operator_claim_ts = 0;
+                table.withdraw_request_ts = 0;
+                table.withdraw_request_amount = 0;
+            }
+            ProposalAction::SetMode { mode } => match change_mode(table, mode, now) {
+                Some(eta) => emit_cpi!(ModeChangeQueued {
+                    table: table.key(),
+                    mode,
+                    eta,
+                }),
+                None => emit_cpi!(ModeChanged {
+                    table: table.key(),
+                    mode,
+                }),
+            },
+            ProposalAction::SetConfig { config } => match change_config(table, config, now)? {
+                Some(eta) => emit_cpi!(TableConfigQueued {
+                    table: table.key(),
+                    config,
+                    eta,
+                }),
+                None => emit_cpi!(TableConfigUpdated {
+                    table: table.key(),
+                    config,
+                }),
+            },
+            ProposalAction::SetPaused { paused } => {
+                set_paused(table, paused);
+                emit_cpi!(Paused {
+                    table: table.key(),
+                    paused,
+                });
+            }
+            ProposalAction::WithdrawLiquidity { amount } => {
+                let (Some(global_state), Some(global_vault_usdc), Some(operator_usdc_ata), Some(token_program)) = (
+                    ctx.accounts.global_state.as_ref(),
+                    ctx.accounts.global_vault_usdc.as_ref(),
+                    ctx.accounts.operator_usdc_ata.as_ref(),
+                    ctx.accounts.token_program.as_ref(),
+                ) else {
+                    return err!(RouletteError::MissingProposalAccounts);
+                };
+                require_keys_eq!(
+                    global_vault_usdc.key(),
+                    global_state.vault_usdc,
+                    RouletteError::VaultMismatch
+                );
+                withdraw_operator_liquidity(
+                    table,
+                    global_state,
+                    global_vault_usdc,
+                    operator_usdc_ata,
+                    token_program,
+                    amount,
+                    now,
+                )?;
+                emit_cpi!(WithdrawExecuted {
+                    table: table.key(),
+                    operator: table.operator,
+                    amount,
+                });
+            }
+        }
 
+        emit_cpi!(ProposalExecuted {
+            table: table.key(),
+            proposal: ctx.accounts.proposal.key(),
+            action: ctx.accounts.proposal.action.clone(),
+        });
         Ok(())
     }
 
-    pub fn refund_expired_bet(ctx: Context<RefundExpiredBet>) -> Result<()> {
+    // --- lifecycle ---
+
+    /// Starts decommissioning the table: no new bets, rounds or deposits are accepted, and
+    /// the operator may withdraw GOV below the threshold. Cannot be undone.
+    pub fn retire_table(ctx: Context<OnlyOperator>) -> Result<()> {
         let table = &mut ctx.accounts.table;
-        let bet = &mut ctx.accounts.bet;
+        require!(!table.retiring, RouletteError::TableRetiring);
+        table.retiring = true;
+        emit_cpi!(TableRetiring { table: table.key() });
+        Ok(())
+    }
 
-        require!(bet.state == BetState::Pending, RouletteError::BetNotPending);
+    /// Closes a retired table once every bet is settled, all deposited GOV is withdrawn and all
+    /// shares are redeemed. Rounding dust left in the table's ledger, GOV sent to the vault
+    /// without a deposit, and the rent of the table and its GOV vault go to the creator. The
+    /// LP mint cannot be closed and is left behind.
+    pub fn close_table(ctx: Context<CloseTable>) -> Result<()> {
+        let table = &ctx.accounts.table;
+        require!(table.retiring, RouletteError::TableNotRetiring);
+        require!(table.active_bets == 0, RouletteError::ActiveBetsExist);
+        // Gate on the ledger, not the vault balance, so stray transfers cannot block closing
+        require!(table.total_gov_deposited == 0, RouletteError::GovStillDeposited);
+        require!(table.total_shares == 0, RouletteError::SharesOutstanding);
+        require!(table.operator_fees == 0, RouletteError::FeesUnclaimed);
+        require!(table.rewards_owed == 0, RouletteError::RewardsUnclaimed);
+
+        let dust = table.liquidity.saturating_add(table.reward_pool);
+        if dust > 0 {
+            transfer_from_vault(
+                &ctx.accounts.global_state,
+                &ctx.accounts.global_vault_usdc,
+                &ctx.accounts.creator_usdc_ata,
+                &ctx.accounts.token_program,
+                dust,
+            )?;
+        }
 
-        let now = Clock::get()?.unix_timestamp;
-        require!(
-            now.saturating_sub(bet.created_ts) >= BET_TIMEOUT_SECONDS,
-            RouletteError::BetNotExpired
-        );
+        let seed_bytes = table.seed.to_le_bytes();
+        let bump = [table.bumps.table];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"table", table.creator.as_ref(), &seed_bytes, &bump]];
 
-        // Refund from global vault (signed by GlobalState PDA)
-        let gs = &mut ctx.accounts.global_state;
-        let signer_seeds: &[&[&[u8]]] = &[&[b"global", gs.usdc_mint.as_ref(), &[gs.bumps.global]]];
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.global_vault_usdc.to_account_info(),
-            to: ctx.accounts.player_usdc_ata.to_account_info(),
-            authority: gs.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts,
-            signer_seeds,
-        );
-        token::transfer(cpi_ctx, bet.stake)?;
+        let stray_gov = ctx.accounts.control_vault_gov.amount;
+        if stray_gov > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.control_vault_gov.to_account_info(),
+                to: ctx.accounts.creator_gov_ata.to_account_info(),
+                authority: table.to_account_info(),
+            };
+            let cpi_ctx =
+                CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+            token::transfer(cpi_ctx, stray_gov)?;
+        }
 
-        table.locked_liability = table.locked_liability.saturating_sub(bet.max_total_payout);
-        table.active_bets = table.active_bets.saturating_sub(1);
-        gs.total_locked_liability = gs.total_locked_liability.saturating_sub(bet.max_total_payout);
-        gs.total_active_bets = gs.total_active_bets.saturating_sub(1);
+        let cpi_accounts = CloseAccount {
+            account: ctx.accounts.control_vault_gov.to_account_info(),
+            destination: ctx.accounts.creator.to_account_info(),
+            authority: table.to_account_info(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+        token::close_account(cpi_ctx)?;
 
-        bet.state = BetState::Refunded;
+        emit_cpi!(TableClosed {
+            table: table.key(),
+            creator: table.creator,
+            swept_liquidity: dust,
+            swept_gov: stray_gov,
+        });
 
         Ok(())
     }
-}
 
-// -------------------- Accounts --------------------
+    // --- liquidity ---
 
+    /// Adds operator capital. Deposits delegated to the operator are passed as remaining
+    /// accounts, since their GOV power must be at the threshold.
+    pub fn deposit_liquidity_usdc<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DepositLiquidityUsdc<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, RouletteError::InvalidAmount);
+        require!(!ctx.accounts.table.retiring, RouletteError::TableRetiring);
+
+        // Only the table operator with enough deposited GOV can manage liquidity
+        let now = Clock::get()?.unix_timestamp;
+        let operator = ctx.accounts.operator.key();
+        let delegated = delegated_deposits(ctx.accounts.table.key(), operator, ctx.remaining_accounts)?;
+        let power = holder_power(&ctx.accounts.operator_gov_deposit, &operator, &delegated, now)?.control;
+        let threshold = operator_threshold(&ctx.accounts.table, ctx.accounts.gov_mint.supply)?;
+        require!(power >= threshold, RouletteError::NotEnoughGovToOperate);
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.operator_usdc_ata.to_account_info(),
+            to: ctx.accounts.global_vault_usdc.to_account_info(),
+            authority: ctx.accounts.operator.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        // Credit this table's sub-ledger inside the shared vault. The operator's capital is
+        // priced in the same shares as LP deposits so both share P&L pro rata.
+        let table = &mut ctx.accounts.table;
+        adopt_orphaned_liquidity(table);
+        let shares = shares_for_deposit(table, amount)?;
+        table.operator_shares = table.operator_shares.saturating_add(shares);
+        table.total_shares = table.total_shares.saturating_add(shares);
+        table.liquidity = table.liquidity.checked_add(amount).ok_or(RouletteError::MathOverflow)?;
+        table.deposited_capital = table.deposited_capital.saturating_add(amount);
+
+        emit_cpi!(LiquidityDeposited {
+            table: table.key(),
+            provider: ctx.accounts.operator.key(),
+            amount,
+            shares,
+        });
+
+        Ok(())
+    }
+
+    pub fn request_withdraw(ctx: Context<OnlyOperator>, amount: u64) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        require!(table.mode == TableMode::Public, RouletteError::NotInPublicMode);
+        require!(amount > 0, RouletteError::InvalidAmount);
+
+        require!(table.active_bets == 0, RouletteError::ActiveBetsExist);
+        require!(table.locked_liability == 0, RouletteError::LiabilityLocked);
+
+        table.withdraw_request_amount = amount;
+        table.withdraw_request_ts = Clock::get()?.unix_timestamp;
+        emit_cpi!(WithdrawRequested {
+            table: table.key(),
+            amount,
+            request_ts: table.withdraw_request_ts,
+        });
+        Ok(())
+    }
+
+    /// Withdraws operator capital. Deposits delegated to the operator are passed as remaining
+    /// accounts, as for `deposit_liquidity_usdc`.
+    pub fn execute_withdraw<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawLiquidityUsdc<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let operator = ctx.accounts.operator.key();
+        let table = &mut ctx.accounts.table;
+        require!(amount > 0, RouletteError::InvalidAmount);
+
+        // Only the table operator with enough deposited GOV can manage liquidity
+        let delegated = delegated_deposits(table.key(), operator, ctx.remaining_accounts)?;
+        let power = holder_power(&ctx.accounts.operator_gov_deposit, &operator, &delegated, now)?.control;
+        let threshold = operator_threshold(table, ctx.accounts.gov_mint.supply)?;
+        require!(power >= threshold, RouletteError::NotEnoughGovToOperate);
+
+        withdraw_operator_liquidity(
+            table,
+            &ctx.accounts.global_state,
+            &ctx.accounts.global_vault_usdc,
+            &ctx.accounts.operator_usdc_ata,
+            &ctx.accounts.token_program,
+            amount,
+            now,
+        )?;
+
+        emit_cpi!(WithdrawExecuted {
+            table: table.key(),
+            operator: ctx.accounts.operator.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Pays out the table's accrued operator fee ledger to the operator.
+    pub fn claim_operator_fees(ctx: Context<ClaimOperatorFees>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        let amount = table.operator_fees;
+        require!(amount > 0, RouletteError::NothingToClaim);
+        table.operator_fees = 0;
+
+        transfer_from_vault(
+            &ctx.accounts.global_state,
+            &ctx.accounts.global_vault_usdc,
+            &ctx.accounts.operator_usdc_ata,
+            &ctx.accounts.token_program,
+            amount,
+        )?;
+
+        emit_cpi!(FeesClaimed {
+            global_state: table.global_state,
+            table: table.key(),
+            recipient: ctx.accounts.operator_usdc_ata.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    // --- public LP vault ---
+
+    /// Opens the table's liquidity to everyone by creating its LP share mint.
+    pub fn init_lp_vault(ctx: Context<InitLpVault>) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        require!(table.lp_mint == Pubkey::default(), RouletteError::LpVaultAlreadyInitialized);
+        table.lp_mint = ctx.accounts.lp_mint.key();
+        emit_cpi!(LpVaultInitialized {
+            table: table.key(),
+            lp_mint: table.lp_mint,
+        });
+        Ok(())
+    }
+
+    /// Deposits USDC into the table's liquidity and mints shares priced by NAV.
+    pub fn deposit_lp(ctx: Context<DepositLp>, amount: u64) -> Result<()> {
+        require!(amount > 0, RouletteError::InvalidAmount);
+        require!(!ctx.accounts.table.retiring, RouletteError::TableRetiring);
+
+        adopt_orphaned_liquidity(&mut ctx.accounts.table);
+        let shares = shares_for_deposit(&ctx.accounts.table, amount)?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.provider_usdc_ata.to_account_info(),
+            to: ctx.accounts.global_vault_usdc.to_account_info(),
+            authority: ctx.accounts.provider.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+
+        let table = &ctx.accounts.table;
+        let seed_bytes = table.seed.to_le_bytes();
+        let bump = [table.bumps.table];
+        let signer_seeds: &[&[&[u8]]] = &[&[b"table", table.creator.as_ref(), &seed_bytes, &bump]];
+        let cpi_accounts = MintTo {
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            to: ctx.accounts.provider_lp_ata.to_account_info(),
+            authority: table.to_account_info(),
+        };
+        let cpi_ctx =
+            CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+        token::mint_to(cpi_ctx, shares)?;
+
+        let table = &mut ctx.accounts.table;
+        table.total_shares = table.total_shares.saturating_add(shares);
+        table.liquidity = table.liquidity.checked_add(amount).ok_or(RouletteError::MathOverflow)?;
+        table.deposited_capital = table.deposited_capital.saturating_add(amount);
+
+        emit_cpi!(LiquidityDeposited {
+            table: table.key(),
+            provider: ctx.accounts.provider.key(),
+            amount,
+            shares,
+        });
+
+        Ok(())
+    }
+
+    /// Starts the withdraw delay for an LP redemption (required in Public mode).
+    pub fn request_lp_redeem(ctx: Context<RequestLpRedeem>, shares: u64) -> Result<()> {
+        require!(shares > 0, RouletteError::InvalidAmount);
+        require!(ctx.accounts.provider_lp_ata.amount >= shares, RouletteError::InsufficientShares);
+
+        let req = &mut ctx.accounts.redeem_request;
+        req.table = ctx.accounts.table.key();
+        req.provider = ctx.accounts.provider.key();
+        req.shares = shares;
+        req.request_ts = Clock::get()?.unix_timestamp;
+        emit_cpi!(LpRedeemRequested {
+            table: req.table,
+            provider: req.provider,
+            shares,
+            request_ts: req.request_ts,
+        });
+        Ok(())
+    }
+
+    /// Burns LP shares and pays out their NAV net of locked liability, so redemptions never
+    /// touch funds reserved for pending bets.
+    pub fn redeem_lp(ctx: Context<RedeemLp>, shares: u64) -> Result<()> {
+        require!(shares > 0, RouletteError::InvalidAmount);
+
+        let table = &ctx.accounts.table;
+        if table.mode == TableMode::Public {
+            let req = ctx
+                .accounts
+                .redeem_request
+                .as_mut()
+                .ok_or(RouletteError::WithdrawRequestMismatch)?;
+            require!(req.shares == shares, RouletteError::WithdrawRequestMismatch);
+            let now = Clock::get()?.unix_timestamp;
+            require!(
+                now.saturating_sub(req.request_ts) >= ctx.accounts.table.withdraw_delay,
+                RouletteError::WithdrawDelayNotPassed
+            );
+            req.shares = 0;
+            req.request_ts = 0;
+        }
+
+        let amount = assets_for_shares(table, shares)?;
+        require!(amount > 0, RouletteError::InvalidAmount);
+
+        let cpi_accounts = Burn {
+            mint: ctx.accounts.lp_mint.to_account_info(),
+            from: ctx.accounts.provider_lp_ata.to_account_info(),
+            authority: ctx.accounts.provider.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::burn(cpi_ctx, shares)?;
+
+        let gs = &ctx.accounts.global_state;
+        let signer_seeds: &[&[&[u8]]] = &[&[b"global", gs.usdc_mint.as_ref(), &[gs.bumps.global]]];
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.global_vault_usdc.to_account_info(),
+            to: ctx.accounts.provider_usdc_ata.to_account_info(),
+            authority: gs.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, signer_seeds);
+        token::transfer(cpi_ctx, amount)?;
+
+        let table = &mut ctx.accounts.table;
+        table.liquidity = table.liquidity.checked_sub(amount).ok_or(RouletteError::MathOverflow)?;
+        table.deposited_capital = table.deposited_capital.saturating_sub(amount);
+        table.total_shares = table.total_shares.checked_sub(shares).ok_or(RouletteError::MathOverflow)?;
+
+        emit_cpi!(LpRedeemed {
+            table: table.key(),
+            provider: ctx.accounts.provider.key(),
+            shares,
+            amount,
+        });
+
+        Ok(())
+    }
+
+    // --- game (USDC + ORAO VRF) ---
+
+    pub fn place_bet(ctx: Context<PlaceBet>, bet: BetKind, stake: u64, force: [u8; 32]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let escrow = open_bet(
+            &mut ctx.accounts.table,
+            &mut ctx.accounts.global_state,
+            &mut ctx.accounts.bet,
+            ctx.accounts.player.key(),
+            vec![BetChip { kind: bet, stake }],
+            ctx.accounts.global_vault_usdc.amount,
+            now,
+        )?;
+        escrow_and_request_spin(
+            &mut ctx.accounts.bet,
+            &ctx.accounts.player,
+            &ctx.accounts.player_usdc_ata,
+            &ctx.accounts.global_vault_usdc,
+            &ctx.accounts.token_program,
+            escrow,
+            &ctx.accounts.random,
+            &ctx.accounts.treasury,
+            &ctx.accounts.config,
+            &ctx.accounts.vrf,
+            &ctx.accounts.system_program,
+            force,
+        )?;
+
+        emit_cpi!(bet_placed_event(&ctx.accounts.bet, ctx.accounts.bet.key()));
+
+        Ok(())
+    }
+
+    /// Places a layout of several chips (e.g. Straight 17 + Red + Dozen 2) as one bet that
+    /// shares a single randomness request and settles once.
+    pub fn place_bets(ctx: Context<PlaceBets>, chips: Vec<BetChip>, force: [u8; 32]) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let escrow = open_bet(
+            &mut ctx.accounts.table,
+            &mut ctx.accounts.global_state,
+            &mut ctx.accounts.bet,
+            ctx.accounts.player.key(),
+            chips,
+            ctx.accounts.global_vault_usdc.amount,
+            now,
+        )?;
+        escrow_and_request_spin(
+            &mut ctx.accounts.bet,
+            &ctx.accounts.player,
+            &ctx.accounts.player_usdc_ata,
+            &ctx.accounts.global_vault_usdc,
+            &ctx.accounts.token_program,
+            escrow,
+            &ctx.accounts.random,
+            &ctx.accounts.treasury,
+            &ctx.accounts.config,
+            &ctx.accounts.vrf,
+            &ctx.accounts.system_program,
+            force,
+        )?;
+
+        emit_cpi!(bet_placed_event(&ctx.accounts.bet, ctx.accounts.bet.key()));
+
+        Ok(())
+    }
+
+    /// Places a racetrack call (Voisins du Zéro, Tiers, Orphelins or neighbours) as one bet.
+    /// The call is expanded into its splits, corners and straights at `unit` stake each,
+    /// and liability is locked for the expanded layout.
+    pub fn place_announced_bet(
+        ctx: Context<PlaceAnnouncedBet>,
+        call: AnnouncedBet,
+        unit: u64,
+        force: [u8; 32],
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let chips = expand_announced_bet(&call, unit, ctx.accounts.table.variant)?;
+        let escrow = open_bet(
+            &mut ctx.accounts.table,
+            &mut ctx.accounts.global_state,
+            &mut ctx.accounts.bet,
+            ctx.accounts.player.key(),
+            chips,
+            ctx.accounts.global_vault_usdc.amount,
+            now,
+        )?;
+        escrow_and_request_spin(
+            &mut ctx.accounts.bet,
+            &ctx.accounts.player,
+            &ctx.accounts.player_usdc_ata,
+            &ctx.accounts.global_vault_usdc,
+            &ctx.accounts.token_program,
+            escrow,
+            &ctx.accounts.random,
+            &ctx.accounts.treasury,
+            &ctx.accounts.config,
+            &ctx.accounts.vrf,
+            &ctx.accounts.system_program,
+            force,
+        )?;
+
+        emit_cpi!(bet_placed_event(&ctx.accounts.bet, ctx.accounts.bet.key()));
+
+        Ok(())
+    }
+
+    pub fn resolve_bet(ctx: Context<ResolveBet>) -> Result<()> {
+        require_awaiting_spin(&ctx.accounts.bet)?;
+        // Read ORAO randomness account and ensure it's fulfilled
+        let rnd = read_randomness(&ctx.accounts.random)?.ok_or(RouletteError::RandomnessNotFulfilled)?;
+
+        let n = roulette_number_from_randomness(&rnd, ctx.accounts.table.variant);
+
+        let settlement = settle_bet(
+            &mut ctx.accounts.table,
+            &mut ctx.accounts.global_state,
+            &mut ctx.accounts.bet,
+            &ctx.accounts.global_vault_usdc,
+            &ctx.accounts.player_usdc_ata,
+            ctx.accounts.resolver_usdc_ata.as_ref().unwrap_or(&ctx.accounts.player_usdc_ata),
+            &ctx.accounts.token_program,
+            n,
+        )?;
+
+        emit_cpi!(bet_resolved_event(&ctx.accounts.bet, ctx.accounts.bet.key(), n, &settlement));
+        if settlement.breaker_tripped {
+            emit_cpi!(breaker_tripped_event(ctx.accounts.global_state.key()));
+        }
+
+        Ok(())
+    }
+
+    /// Settles a batch of single-spin bets in one transaction. Remaining accounts come in
+    /// `(bet, randomness, player token account)` triples; every bet is paid out on its own
+    /// outcome, while the liability counters and the breaker are updated once for the batch.
+    pub fn resolve_bets<'info>(ctx: Context<'_, '_, 'info, 'info, ResolveBets<'info>>) -> Result<()> {
+        let batch = ctx.remaining_accounts;
+        require!(
+            !batch.is_empty() && batch.chunks_exact(3).remainder().is_empty(),
+            RouletteError::InvalidBatchAccounts
+        );
+        let table_key = ctx.accounts.table.key();
+        let usdc_mint = ctx.accounts.table.usdc_mint;
+
+        let mut settled: u32 = 0;
+        let mut paid_out: u64 = 0;
+        for triple in batch.chunks_exact(3) {
+            let mut bet = Account::<BetAccount>::try_from(&triple[0])?;
+            require_keys_eq!(bet.table, table_key, RouletteError::BetTableMismatch);
+            require_awaiting_spin(&bet)?;
+            require_keys_eq!(bet.round, Pubkey::default(), RouletteError::RoundBetInBatch);
+            require_keys_eq!(
+                triple[1].key(),
+                bet.randomness_account,
+                RouletteError::RandomnessAccountMismatch
+            );
+            let player_usdc_ata = Account::<TokenAccount>::try_from(&triple[2])?;
+            require_keys_eq!(player_usdc_ata.owner, bet.player, RouletteError::PlayerTokenAccountMismatch);
+            require_keys_eq!(player_usdc_ata.mint, usdc_mint, RouletteError::TokenMintMismatch);
+
+            let rnd = read_randomness(&triple[1])?.ok_or(RouletteError::RandomnessNotFulfilled)?;
+            let n = roulette_number_from_randomness(&rnd, ctx.accounts.table.variant);
+
+            let settlement = pay_out_bet(
+                &mut ctx.accounts.table,
+                &mut ctx.accounts.global_state,
+                &mut bet,
+                &ctx.accounts.global_vault_usdc,
+                &player_usdc_ata,
+                ctx.accounts.resolver_usdc_ata.as_ref().unwrap_or(&player_usdc_ata),
+                &ctx.accounts.token_program,
+                n,
+            )?;
+            settled += u32::from(settlement.imprisoned_stake == 0);
+            paid_out = paid_out
+                .saturating_add(settlement.amount)
+                .saturating_add(settlement.tip);
+
+            // Persist now so a bet repeated later in the batch is seen as already resolved
+            bet.exit(&crate::ID)?;
+            emit_cpi!(bet_resolved_event(&bet, bet.key(), n, &settlement));
+        }
+
+        release_liability(
+            &mut ctx.accounts.table,
+            &mut ctx.accounts.global_state,
+            settled,
+        );
+        let vault_balance = ctx.accounts.global_vault_usdc.amount.saturating_sub(paid_out);
+        if trip_breaker_if_below_floor(&mut ctx.accounts.global_state, vault_balance) {
+            emit_cpi!(breaker_tripped_event(ctx.accounts.global_state.key()));
+        }
+
+        Ok(())
+    }
+
+    /// Refunds a bet whose randomness never arrived. If ORAO has already fulfilled the
+    /// request the bet is settled with that outcome instead, so a losing player cannot
+    /// sit on a known result and reclaim the stake after the timeout.
+    pub fn refund_expired_bet(ctx: Context<RefundExpiredBet>) -> Result<()> {
+        let bet = &ctx.accounts.bet;
+
+        require_awaiting_spin(bet)?;
+        // Round bets are refunded through `refund_round_bet`, which checks the round
+        require!(bet.round == Pubkey::default(), RouletteError::RoundBetMismatch);
+
+        let randomness = read_randomness(&ctx.accounts.random)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        let settlement = match refund_decision(
+            randomness,
+            ctx.accounts.table.variant,
+            bet.created_ts,
+            bet.bet_timeout,
+            now,
+        )? {
+            RefundDecision::Settle(n) => {
+                let settlement = settle_bet(
+                    &mut ctx.accounts.table,
+                    &mut ctx.accounts.global_state,
+                    &mut ctx.accounts.bet,
+                    &ctx.accounts.global_vault_usdc,
+                    &ctx.accounts.player_usdc_ata,
+                    ctx.accounts.resolver_usdc_ata.as_ref().unwrap_or(&ctx.accounts.player_usdc_ata),
+                    &ctx.accounts.token_program,
+                    n,
+                )?;
+                emit_cpi!(bet_resolved_event(&ctx.accounts.bet, ctx.accounts.bet.key(), n, &settlement));
+                settlement
+            }
+            RefundDecision::Refund => {
+                let settlement = refund_bet(
+                    &mut ctx.accounts.table,
+                    &mut ctx.accounts.global_state,
+                    &mut ctx.accounts.bet,
+                    &ctx.accounts.global_vault_usdc,
+                    &ctx.accounts.player_usdc_ata,
+                    ctx.accounts.resolver_usdc_ata.as_ref().unwrap_or(&ctx.accounts.player_usdc_ata),
+                    &ctx.accounts.token_program,
+                )?;
+                emit_cpi!(bet_refunded_event(&ctx.accounts.bet, ctx.accounts.bet.key()));
+                settlement
+            }
+        };
+        if settlement.breaker_tripped {
+            emit_cpi!(breaker_tripped_event(ctx.accounts.global_state.key()));
+        }
+
+        Ok(())
+    }
+
+    /// Closes a resolved or refunded bet and returns its rent to the player.
+    /// Bet PDAs are keyed by the table's monotonically increasing `bet_seq`, so a closed
+    /// address is never derived again. The ORAO randomness account is owned by the VRF
+    /// program, which has no close instruction; its rent stays with ORAO.
+    pub fn close_bet(ctx: Context<CloseBet>) -> Result<()> {
+        emit_cpi!(BetClosed {
+            table: ctx.accounts.bet.table,
+            bet: ctx.accounts.bet.key(),
+            player: ctx.accounts.player.key(),
+        });
+        Ok(())
+    }
+
+    /// Requests the next spin for an imprisoned bet. Anyone may pay for it; the outcome then
+    /// settles through `resolve_bet` or `refund_expired_bet` like any other bet. A prisoner
+    /// left unspun past the bet timeout is settled by `release_imprisoned_bet` instead.
+    /// A prisoner from a shared-spin round passes the round and leaves it here.
+    pub fn spin_imprisoned_bet(ctx: Context<SpinImprisonedBet>, force: [u8; 32]) -> Result<()> {
+        prisoner_leaves_round(ctx.accounts.round.as_deref_mut(), &mut ctx.accounts.bet)?;
+        let bet = &mut ctx.accounts.bet;
+        bet.force = force;
+        bet.randomness_account = ctx.accounts.random.key();
+        bet.created_ts = Clock::get()?.unix_timestamp;
+
+        let cpi_program = ctx.accounts.vrf.to_account_info();
+        let cpi_accounts = orao_cpi::accounts::RequestV2 {
+            payer: ctx.accounts.payer.to_account_info(),
+            network_state: ctx.accounts.config.clone(),
+            treasury: ctx.accounts.treasury.clone(),
+            request: ctx.accounts.random.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        orao_cpi::request_v2(cpi_ctx, force)?;
+
+        emit_cpi!(ImprisonedBetSpun {
+            table: ctx.accounts.bet.table,
+            bet: ctx.accounts.bet.key(),
+            randomness_account: ctx.accounts.random.key(),
+        });
+        Ok(())
+    }
+
+    /// Settles an imprisoned bet nobody spun within its bet timeout the La Partage way: the
+    /// player gets half of each chip's stake back and the house keeps the rest. Anyone may
+    /// crank it, so an abandoned prisoner cannot hold the table's active bets open forever.
+    pub fn release_imprisoned_bet(ctx: Context<ReleaseImprisonedBet>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let bet = &ctx.accounts.bet;
+        require!(
+            now.saturating_sub(bet.created_ts) >= bet.bet_timeout,
+            RouletteError::BetNotExpired
+        );
+
+        let settlement = release_prisoner(
+            &mut ctx.accounts.table,
+            &mut ctx.accounts.global_state,
+            &mut ctx.accounts.bet,
+            &ctx.accounts.global_vault_usdc,
+            &ctx.accounts.player_usdc_ata,
+            &ctx.accounts.token_program,
+        )?;
+        prisoner_leaves_round(ctx.accounts.round.as_deref_mut(), &mut ctx.accounts.bet)?;
+        emit_cpi!(ImprisonedBetReleased {
+            table: ctx.accounts.bet.table,
+            bet: ctx.accounts.bet.key(),
+            player: ctx.accounts.bet.player,
+            amount: settlement.amount,
+        });
+        if settlement.breaker_tripped {
+            emit_cpi!(breaker_tripped_event(ctx.accounts.global_state.key()));
+        }
+        Ok(())
+    }
+
+    /// Read-only view of the current stake limit for each bet kind, so clients can show live
+    /// limits instead of submitting bets that fail. Limits are for a single chip on an
+    /// otherwise empty number; opposing bets can leave room for more.
+    pub fn bet_limits(ctx: Context<TableView>) -> Result<Vec<BetLimit>> {
+        Ok(bet_limits_for(&ctx.accounts.table))
+    }
+
+    // --- shared-spin rounds ---
+
+    /// Opens a betting window on the table. Every bet placed into the round settles against
+    /// the single outcome requested by `close_round`.
+    pub fn open_round(ctx: Context<OpenRound>, betting_window: i64) -> Result<()> {
+        let table = &mut ctx.accounts.table;
+        require!(!table.paused, RouletteError::Paused);
+        require!(!table.retiring, RouletteError::TableRetiring);
+        require!(betting_window > 0, RouletteError::InvalidBettingWindow);
+
+        let now = Clock::get()?.unix_timestamp;
+        let round = &mut ctx.accounts.round;
+        round.table = table.key();
+        round.round_id = table.round_seq;
+        round.state = RoundState::Open;
+        round.opened_ts = now;
+        round.closes_ts = now.saturating_add(betting_window);
+        round.bet_timeout = table.bet_timeout;
+        round.force = [0u8; 32];
+        round.randomness_account = Pubkey::default();
+        round.result_number = None;
+        round.bet_count = 0;
+        round.open_bets = 0;
+        round.imprisoned_bets = 0;
+
+        table.round_seq = table.round_seq.saturating_add(1);
+        emit_cpi!(RoundOpened {
+            table: round.table,
+            round: round.key(),
+            round_id: round.round_id,
+            closes_ts: round.closes_ts,
+        });
+        Ok(())
+    }
+
+    pub fn place_round_bet(ctx: Context<PlaceRoundBet>, bet: BetKind, stake: u64) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let round = &mut ctx.accounts.round;
+        require!(round.state == RoundState::Open, RouletteError::RoundNotOpen);
+        require!(now < round.closes_ts, RouletteError::RoundNotOpen);
+
+        let escrow = open_bet(
+            &mut ctx.accounts.table,
+            &mut ctx.accounts.global_state,
+            &mut ctx.accounts.bet,
+            ctx.accounts.player.key(),
+            vec![BetChip { kind: bet, stake }],
+            ctx.accounts.global_vault_usdc.amount,
+            now,
+        )?;
+
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.player_usdc_ata.to_account_info(),
+            to: ctx.accounts.global_vault_usdc.to_account_info(),
+            authority: ctx.accounts.player.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, escrow)?;
+
+        ctx.accounts.bet.round = round.key();
+        round.bet_count = round.bet_count.saturating_add(1);
+        round.open_bets = round.open_bets.saturating_add(1);
+        emit_cpi!(bet_placed_event(&ctx.accounts.bet, ctx.accounts.bet.key()));
+        Ok(())
+    }
+
+    /// Closes betting once the window has elapsed and requests the round's single VRF.
+    /// The `force` seed is only chosen here, so nobody can pre-request it while bets are open.
+    pub fn close_round(ctx: Context<CloseRound>, force: [u8; 32]) -> Result<()> {
+        let round = &mut ctx.accounts.round;
+        require!(round.state == RoundState::Open, RouletteError::RoundNotOpen);
+        let now = Clock::get()?.unix_timestamp;
+        require!(now >= round.closes_ts, RouletteError::RoundStillOpen);
+
+        round.state = RoundState::Closed;
+        round.force = force;
+        round.randomness_account = ctx.accounts.random.key();
+
+        let cpi_program = ctx.accounts.vrf.to_account_info();
+        let cpi_accounts = orao_cpi::accounts::RequestV2 {
+            payer: ctx.accounts.payer.to_account_info(),
+            network_state: ctx.accounts.config.clone(),
+            treasury: ctx.accounts.treasury.clone(),
+            request: ctx.accounts.random.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        orao_cpi::request_v2(cpi_ctx, force)?;
+
+        emit_cpi!(RoundClosed {
+            table: ctx.accounts.round.table,
+            round: ctx.accounts.round.key(),
+            randomness_account: ctx.accounts.random.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Records the round's outcome once ORAO has fulfilled the request.
+    pub fn settle_round(ctx: Context<SettleRound>) -> Result<()> {
+        let round = &mut ctx.accounts.round;
+        require!(round.state == RoundState::Closed, RouletteError::RoundNotClosed);
+
+        let rnd = read_randomness(&ctx.accounts.random)?.ok_or(RouletteError::RandomnessNotFulfilled)?;
+
+        let n = roulette_number_from_randomness(&rnd, ctx.accounts.table.variant);
+        round.result_number = Some(n);
+        round.state = RoundState::Settled;
+        emit_cpi!(RoundSettled {
+            table: round.table,
+            round: round.key(),
+            result_number: n,
+        });
+        Ok(())
+    }
+
+    pub fn resolve_round_bet(ctx: Context<ResolveRoundBet>) -> Result<()> {
+        require!(ctx.accounts.bet.state == BetState::Pending, RouletteError::BetNotPending);
+        let round = &ctx.accounts.round;
+        require!(round.state == RoundState::Settled, RouletteError::RoundNotSettled);
+        let n = round.result_number.ok_or(RouletteError::RoundNotSettled)?;
+
+        let settlement = settle_bet(
+            &mut ctx.accounts.table,
+            &mut ctx.accounts.global_state,
+            &mut ctx.accounts.bet,
+            &ctx.accounts.global_vault_usdc,
+            &ctx.accounts.player_usdc_ata,
+            ctx.accounts.resolver_usdc_ata.as_ref().unwrap_or(&ctx.accounts.player_usdc_ata),
+            &ctx.accounts.token_program,
+            n,
+        )?;
+        round_bet_settled(&mut ctx.accounts.round, &ctx.accounts.bet);
+
+        emit_cpi!(bet_resolved_event(&ctx.accounts.bet, ctx.accounts.bet.key(), n, &settlement));
+        if settlement.breaker_tripped {
+            emit_cpi!(breaker_tripped_event(ctx.accounts.global_state.key()));
+        }
+
+        Ok(())
+    }
+
+    /// Refunds a round bet when the round never produced an outcome in time. A closed round
+    /// whose randomness is already fulfilled is settled instead, exactly like `settle_round`.
+    pub fn refund_round_bet(ctx: Context<RefundRoundBet>) -> Result<()> {
+        require!(ctx.accounts.bet.state == BetState::Pending, RouletteError::BetNotPending);
+        let round = &mut ctx.accounts.round;
+        require!(round.state != RoundState::Settled, RouletteError::RoundAlreadySettled);
+
+        // An open round never requested randomness, so there is nothing to peek at
+        let randomness = if round.state == RoundState::Closed {
+            let random = ctx.accounts.random.as_ref().ok_or(RouletteError::RandomnessAccountMismatch)?;
+            read_randomness(random)?
+        } else {
+            None
+        };
+        let now = Clock::get()?.unix_timestamp;
+
+        let settlement = if let RefundDecision::Settle(n) = refund_decision(
+            randomness,
+            ctx.accounts.table.variant,
+            round.closes_ts,
+            round.bet_timeout,
+            now,
+        )? {
+            round.result_number = Some(n);
+            round.state = RoundState::Settled;
+            emit_cpi!(RoundSettled {
+                table: round.table,
+                round: round.key(),
+                result_number: n,
+            });
+            let settlement = settle_bet(
+                &mut ctx.accounts.table,
+                &mut ctx.accounts.global_state,
+                &mut ctx.accounts.bet,
+                &ctx.accounts.global_vault_usdc,
+                &ctx.accounts.player_usdc_ata,
+                ctx.accounts.resolver_usdc_ata.as_ref().unwrap_or(&ctx.accounts.player_usdc_ata),
+                &ctx.accounts.token_program,
+                n,
+            )?;
+            emit_cpi!(bet_resolved_event(&ctx.accounts.bet, ctx.accounts.bet.key(), n, &settlement));
+            settlement
+        } else {
+            let settlement = refund_bet(
+                &mut ctx.accounts.table,
+                &mut ctx.accounts.global_state,
+                &mut ctx.accounts.bet,
+                &ctx.accounts.global_vault_usdc,
+                &ctx.accounts.player_usdc_ata,
+                ctx.accounts.resolver_usdc_ata.as_ref().unwrap_or(&ctx.accounts.player_usdc_ata),
+                &ctx.accounts.token_program,
+            )?;
+            emit_cpi!(bet_refunded_event(&ctx.accounts.bet, ctx.accounts.bet.key()));
+            settlement
+        };
+        round_bet_settled(&mut ctx.accounts.round, &ctx.accounts.bet);
+        if settlement.breaker_tripped {
+            emit_cpi!(breaker_tripped_event(ctx.accounts.global_state.key()));
+        }
+
+        Ok(())
+    }
+}
+
+// -------------------- Accounts --------------------
+
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(seed: u64)]
 pub struct CreateTable<'info> {
@@ -513,6 +1948,12 @@ pub struct InitGlobal<'info> {
         bump
     )]
     pub global_state: Account<'info, GlobalState>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ RouletteError::Unauthorized)]
+    pub program: Program<'info, crate::program::RouletteTable>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(payer.key()) @ RouletteError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
     #[account(
         init,
         payer = payer,
@@ -542,6 +1983,11 @@ pub struct RepairGlobal<'info> {
     /// CHECK: Program-owned GlobalState PDA; we validate `owner == crate::ID` and then rewrite/realloc its data.
     pub global_state: UncheckedAccount<'info>,
 
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ RouletteError::Unauthorized)]
+    pub program: Program<'info, crate::program::RouletteTable>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(payer.key()) @ RouletteError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
     #[account(
         init_if_needed,
         payer = payer,
@@ -557,29 +2003,198 @@ pub struct RepairGlobal<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct OnlyOperator<'info> {
-    #[account(mut)]
-    pub operator: Signer<'info>,
-    #[account(mut, has_one = operator)]
-    pub table: Account<'info, Table>,
+pub struct GlobalAdmin<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut, has_one = admin @ RouletteError::Unauthorized)]
+    pub global_state: Account<'info, GlobalState>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct DepositLiquidityUsdc<'info> {
-    #[account(mut)]
-    pub operator: Signer<'info>,
-    #[account(mut)]
-    pub operator_usdc_ata: Account<'info, TokenAccount>,
+pub struct GlobalFeeManager<'info> {
+    pub fee_manager: Signer<'info>,
+    #[account(
+        mut,
+        constraint = global_state.has_role(GlobalRole::FeeManager, &fee_manager.key()) @ RouletteError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimProtocolFees<'info> {
+    pub fee_manager: Signer<'info>,
+    #[account(
+        mut,
+        constraint = global_state.has_role(GlobalRole::FeeManager, &fee_manager.key()) @ RouletteError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc @ RouletteError::VaultMismatch)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+    #[account(mut, constraint = recipient_usdc_ata.mint == global_state.usdc_mint @ RouletteError::TokenMintMismatch)]
+    pub recipient_usdc_ata: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+    #[account(mut, has_one = pending_admin @ RouletteError::Unauthorized)]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct GlobalPauser<'info> {
+    pub pauser: Signer<'info>,
+    #[account(
+        mut,
+        constraint = global_state.has_role(GlobalRole::Pauser, &pauser.key()) @ RouletteError::Unauthorized
+    )]
+    pub global_state: Account<'info, GlobalState>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TripCircuitBreaker<'info> {
+    #[account(mut)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(address = global_state.vault_usdc @ RouletteError::VaultMismatch)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+}
 
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OnlyOperator<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
     #[account(mut, has_one = operator)]
     pub table: Account<'info, Table>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub proposer: Signer<'info>,
+    #[account(mut)]
+    pub table: Account<'info, Table>,
+    #[account(
+        seeds = [b"gov_deposit", table.key().as_ref(), proposer.key().as_ref()],
+        bump,
+        constraint = gov_deposit.depositor == proposer.key()
+    )]
+    pub gov_deposit: Account<'info, GovDeposit>,
+    #[account(address = table.gov_mint @ RouletteError::GovMintMismatch)]
+    pub gov_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = proposer,
+        space = 8 + Proposal::SIZE,
+        seeds = [b"proposal", table.key().as_ref(), &table.proposal_seq.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Vote<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+    pub table: Account<'info, Table>,
+    #[account(mut, has_one = table @ RouletteError::ProposalTableMismatch)]
+    pub proposal: Account<'info, Proposal>,
+    #[account(
+        mut,
+        seeds = [b"gov_deposit", table.key().as_ref(), voter.key().as_ref()],
+        bump,
+        constraint = gov_deposit.depositor == voter.key()
+    )]
+    pub gov_deposit: Account<'info, GovDeposit>,
+
+    /// One record per voter and proposal; `init` rejects a second vote.
+    #[account(
+        init,
+        payer = voter,
+        space = 8 + VoteRecord::SIZE,
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    pub executor: Signer<'info>,
+    #[account(mut)]
+    pub table: Account<'info, Table>,
+    #[account(mut, has_one = table @ RouletteError::ProposalTableMismatch)]
+    pub proposal: Account<'info, Proposal>,
 
+    #[account(mut, address = table.global_state @ RouletteError::GlobalStateMismatch)]
+    pub global_state: Option<Account<'info, GlobalState>>,
+    #[account(mut)]
+    pub global_vault_usdc: Option<Account<'info, TokenAccount>>,
     #[account(
         mut,
-        constraint = operator_gov_ata.mint == table.gov_mint
+        constraint = operator_usdc_ata.owner == table.operator @ RouletteError::OperatorTokenAccountMismatch,
+        constraint = operator_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch
+    )]
+    pub operator_usdc_ata: Option<Account<'info, TokenAccount>>,
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseTable<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(mut, has_one = creator, has_one = control_vault_gov, close = creator)]
+    pub table: Account<'info, Table>,
+    #[account(mut)]
+    pub control_vault_gov: Account<'info, TokenAccount>,
+
+    #[account(address = table.global_state @ RouletteError::GlobalStateMismatch)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc @ RouletteError::VaultMismatch)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+    #[account(mut, constraint = creator_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch)]
+    pub creator_usdc_ata: Account<'info, TokenAccount>,
+    #[account(mut, constraint = creator_gov_ata.mint == table.gov_mint @ RouletteError::TokenMintMismatch)]
+    pub creator_gov_ata: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositLiquidityUsdc<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    #[account(mut)]
+    pub operator_usdc_ata: Account<'info, TokenAccount>,
+
+    #[account(mut, has_one = operator)]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        seeds = [b"gov_deposit", table.key().as_ref(), operator.key().as_ref()],
+        bump,
+        constraint = operator_gov_deposit.depositor == operator.key()
     )]
-    pub operator_gov_ata: Account<'info, TokenAccount>,
+    pub operator_gov_deposit: Account<'info, GovDeposit>,
+    #[account(address = table.gov_mint @ RouletteError::GovMintMismatch)]
+    pub gov_mint: Account<'info, Mint>,
 
     #[account(mut, address = table.global_state)]
     pub global_state: Account<'info, GlobalState>,
@@ -588,6 +2203,7 @@ pub struct DepositLiquidityUsdc<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct WithdrawLiquidityUsdc<'info> {
     #[account(mut)]
@@ -597,19 +2213,139 @@ pub struct WithdrawLiquidityUsdc<'info> {
     #[account(mut, has_one = operator)]
     pub table: Account<'info, Table>,
 
+    #[account(
+        seeds = [b"gov_deposit", table.key().as_ref(), operator.key().as_ref()],
+        bump,
+        constraint = operator_gov_deposit.depositor == operator.key()
+    )]
+    pub operator_gov_deposit: Account<'info, GovDeposit>,
+    #[account(address = table.gov_mint @ RouletteError::GovMintMismatch)]
+    pub gov_mint: Account<'info, Mint>,
+
+    #[account(mut, address = table.global_state)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimOperatorFees<'info> {
+    pub operator: Signer<'info>,
+    #[account(mut, has_one = operator)]
+    pub table: Account<'info, Table>,
     #[account(
         mut,
-        constraint = operator_gov_ata.mint == table.gov_mint
+        constraint = operator_usdc_ata.owner == operator.key() @ RouletteError::OperatorTokenAccountMismatch,
+        constraint = operator_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch
+    )]
+    pub operator_usdc_ata: Account<'info, TokenAccount>,
+
+    #[account(address = table.global_state @ RouletteError::GlobalStateMismatch)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc @ RouletteError::VaultMismatch)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitLpVault<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    #[account(mut, has_one = operator, has_one = usdc_mint)]
+    pub table: Account<'info, Table>,
+
+    pub usdc_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = operator,
+        mint::decimals = usdc_mint.decimals,
+        mint::authority = table,
+        seeds = [b"lp_mint", table.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Account<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DepositLp<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+    #[account(mut)]
+    pub provider_usdc_ata: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = lp_mint)]
+    pub provider_lp_ata: Account<'info, TokenAccount>,
+
+    #[account(mut, has_one = lp_mint, has_one = global_state)]
+    pub table: Account<'info, Table>,
+    #[account(mut)]
+    pub lp_mint: Account<'info, Mint>,
+
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RequestLpRedeem<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+    #[account(token::mint = table.lp_mint, token::authority = provider)]
+    pub provider_lp_ata: Account<'info, TokenAccount>,
+
+    pub table: Account<'info, Table>,
+
+    #[account(
+        init_if_needed,
+        payer = provider,
+        space = 8 + LpRedeemRequest::SIZE,
+        seeds = [b"lp_redeem", table.key().as_ref(), provider.key().as_ref()],
+        bump
     )]
-    pub operator_gov_ata: Account<'info, TokenAccount>,
+    pub redeem_request: Account<'info, LpRedeemRequest>,
 
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RedeemLp<'info> {
+    #[account(mut)]
+    pub provider: Signer<'info>,
+    #[account(mut)]
+    pub provider_usdc_ata: Account<'info, TokenAccount>,
+    #[account(mut, token::mint = lp_mint)]
+    pub provider_lp_ata: Account<'info, TokenAccount>,
+
+    #[account(mut, has_one = lp_mint, has_one = global_state)]
+    pub table: Account<'info, Table>,
     #[account(mut)]
+    pub lp_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_redeem", table.key().as_ref(), provider.key().as_ref()],
+        bump
+    )]
+    pub redeem_request: Option<Account<'info, LpRedeemRequest>>,
+
     pub global_state: Account<'info, GlobalState>,
     #[account(mut, address = global_state.vault_usdc)]
     pub global_vault_usdc: Account<'info, TokenAccount>,
     pub token_program: Program<'info, Token>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct DepositGov<'info> {
     #[account(mut)]
@@ -636,6 +2372,7 @@ pub struct DepositGov<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct WithdrawGov<'info> {
     #[account(mut)]
@@ -648,6 +2385,8 @@ pub struct WithdrawGov<'info> {
 
     #[account(mut, address = table.control_vault_gov)]
     pub control_vault_gov: Account<'info, TokenAccount>,
+    #[account(address = table.gov_mint @ RouletteError::GovMintMismatch)]
+    pub gov_mint: Account<'info, Mint>,
 
     #[account(
         mut,
@@ -660,277 +2399,2668 @@ pub struct WithdrawGov<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct ClaimOperator<'info> {
+pub struct ClaimRewards<'info> {
+    pub depositor: Signer<'info>,
     #[account(mut)]
     pub table: Account<'info, Table>,
     #[account(
+        mut,
         seeds = [b"gov_deposit", table.key().as_ref(), depositor.key().as_ref()],
         bump,
         constraint = gov_deposit.depositor == depositor.key()
     )]
     pub gov_deposit: Account<'info, GovDeposit>,
-    /// CHECK: only for seeds
-    pub depositor: AccountInfo<'info>,
-}
-
-#[derive(Accounts)]
-#[instruction(bet: BetKind, stake: u64, force: [u8; 32])]
-pub struct PlaceBet<'info> {
-    #[account(mut)]
-    pub player: Signer<'info>,
-    #[account(mut)]
-    pub player_usdc_ata: Account<'info, TokenAccount>,
+    #[account(mut, constraint = depositor_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch)]
+    pub depositor_usdc_ata: Account<'info, TokenAccount>,
 
-    #[account(mut)]
-    pub table: Account<'info, Table>,
-    #[account(mut)]
+    #[account(address = table.global_state @ RouletteError::GlobalStateMismatch)]
     pub global_state: Account<'info, GlobalState>,
-    #[account(mut, address = global_state.vault_usdc)]
+    #[account(mut, address = global_state.vault_usdc @ RouletteError::VaultMismatch)]
     pub global_vault_usdc: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
 
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ManageGovDeposit<'info> {
+    pub depositor: Signer<'info>,
+    pub table: Account<'info, Table>,
     #[account(
-        init,
-        payer = player,
-        space = 8 + BetAccount::SIZE,
-        seeds = [b"bet", table.key().as_ref(), player.key().as_ref(), &table.bet_seq.to_le_bytes()],
-        bump
+        mut,
+        seeds = [b"gov_deposit", table.key().as_ref(), depositor.key().as_ref()],
+        bump,
+        constraint = gov_deposit.depositor == depositor.key()
     )]
-    pub bet: Account<'info, BetAccount>,
+    pub gov_deposit: Account<'info, GovDeposit>,
+}
 
-    /// CHECK: ORAO VRF randomness account (temporarily unchecked during discriminator fix)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct DelegateGov<'info> {
+    pub depositor: Signer<'info>,
+    pub table: Account<'info, Table>,
+    #[account(address = table.gov_mint @ RouletteError::GovMintMismatch)]
+    pub gov_mint: Account<'info, Mint>,
     #[account(
         mut,
-        seeds = [RANDOMNESS_ACCOUNT_SEED, &force],
+        seeds = [b"gov_deposit", table.key().as_ref(), depositor.key().as_ref()],
         bump,
-        seeds::program = orao_solana_vrf::ID
+        constraint = gov_deposit.depositor == depositor.key()
     )]
-    pub random: AccountInfo<'info>,
+    pub gov_deposit: Account<'info, GovDeposit>,
+}
 
-    /// CHECK: treasury (placeholder)
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseGovDeposit<'info> {
     #[account(mut)]
-    pub treasury: AccountInfo<'info>,
-
+    pub depositor: Signer<'info>,
     #[account(
         mut,
-        seeds = [CONFIG_ACCOUNT_SEED],
+        seeds = [b"gov_deposit", gov_deposit.table.as_ref(), depositor.key().as_ref()],
         bump,
-        seeds::program = orao_solana_vrf::ID
+        has_one = depositor,
+        constraint = gov_deposit.amount == 0 @ RouletteError::GovDepositNotEmpty,
+        constraint = gov_deposit.pending_rewards == 0 @ RouletteError::RewardsUnclaimed,
+        close = depositor
     )]
-    /// CHECK: ORAO VRF config PDA (validated by seeds::program + seeds). We don't deserialize to avoid layout mismatches.
-    pub config: AccountInfo<'info>,
-
-    pub vrf: Program<'info, OraoVrfProgram>,
-
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub gov_deposit: Account<'info, GovDeposit>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
-pub struct ResolveBet<'info> {
-    #[account(mut)]
-    pub resolver: Signer<'info>,
+pub struct ClaimOperator<'info> {
+    pub claimant: Signer<'info>,
     #[account(mut)]
     pub table: Account<'info, Table>,
-    #[account(mut)]
-    pub bet: Account<'info, BetAccount>,
+    #[account(
+        seeds = [b"gov_deposit", table.key().as_ref(), claimant.key().as_ref()],
+        bump,
+        constraint = gov_deposit.depositor == claimant.key()
+    )]
+    pub gov_deposit: Account<'info, GovDeposit>,
+    #[account(address = table.gov_mint @ RouletteError::GovMintMismatch)]
+    pub gov_mint: Account<'info, Mint>,
+}
 
-    #[account(mut)]
-    pub player_usdc_ata: Account<'info, TokenAccount>,
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ChallengeOperatorClaim<'info> {
+    pub operator: Signer<'info>,
+    #[account(mut, has_one = operator)]
+    pub table: Account<'info, Table>,
+    #[account(address = table.gov_mint @ RouletteError::GovMintMismatch)]
+    pub gov_mint: Account<'info, Mint>,
+    #[account(
+        seeds = [b"gov_deposit", table.key().as_ref(), operator.key().as_ref()],
+        bump,
+        constraint = gov_deposit.depositor == operator.key()
+    )]
+    pub gov_deposit: Account<'info, GovDeposit>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeOperatorClaim<'info> {
+    pub claimant: Signer<'info>,
+    #[account(
+        mut,
+        constraint = table.pending_operator == claimant.key() @ RouletteError::NoOperatorClaim
+    )]
+    pub table: Account<'info, Table>,
+    #[account(
+        seeds = [b"gov_deposit", table.key().as_ref(), claimant.key().as_ref()],
+        bump,
+        constraint = gov_deposit.depositor == claimant.key()
+    )]
+    pub gov_deposit: Account<'info, GovDeposit>,
+    #[account(address = table.gov_mint @ RouletteError::GovMintMismatch)]
+    pub gov_mint: Account<'info, Mint>,
+
+    #[account(address = table.global_state @ RouletteError::GlobalStateMismatch)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc @ RouletteError::VaultMismatch)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+    /// Receives the outgoing operator's capital.
+    #[account(
+        mut,
+        constraint = previous_operator_usdc_ata.owner == table.operator @ RouletteError::OperatorTokenAccountMismatch,
+        constraint = previous_operator_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch
+    )]
+    pub previous_operator_usdc_ata: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(bet: BetKind, stake: u64, force: [u8; 32])]
+pub struct PlaceBet<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(mut)]
+    pub player_usdc_ata: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub table: Account<'info, Table>,
+    #[account(mut, address = table.global_state)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + BetAccount::space(1),
+        seeds = [b"bet", table.key().as_ref(), player.key().as_ref(), &table.bet_seq.to_le_bytes()],
+        bump
+    )]
+    pub bet: Account<'info, BetAccount>,
+
+    /// CHECK: ORAO VRF randomness account (temporarily unchecked during discriminator fix)
+    #[account(
+        mut,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, &force],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    pub random: AccountInfo<'info>,
+
+    /// CHECK: treasury (placeholder)
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    /// CHECK: ORAO VRF config PDA (validated by seeds::program + seeds). We don't deserialize to avoid layout mismatches.
+    pub config: AccountInfo<'info>,
+
+    pub vrf: Program<'info, OraoVrfProgram>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(chips: Vec<BetChip>, force: [u8; 32])]
+pub struct PlaceBets<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(mut)]
+    pub player_usdc_ata: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub table: Account<'info, Table>,
+    #[account(mut, address = table.global_state)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + BetAccount::space(chips.len()),
+        seeds = [b"bet", table.key().as_ref(), player.key().as_ref(), &table.bet_seq.to_le_bytes()],
+        bump
+    )]
+    pub bet: Account<'info, BetAccount>,
+
+    /// CHECK: ORAO VRF randomness account
+    #[account(
+        mut,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, &force],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    pub random: AccountInfo<'info>,
+
+    /// CHECK: treasury (placeholder)
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    /// CHECK: ORAO VRF config PDA (validated by seeds::program + seeds).
+    pub config: AccountInfo<'info>,
+
+    pub vrf: Program<'info, OraoVrfProgram>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(call: AnnouncedBet, unit: u64, force: [u8; 32])]
+pub struct PlaceAnnouncedBet<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(mut)]
+    pub player_usdc_ata: Account<'info, TokenAccount>,
 
     #[account(mut)]
+    pub table: Account<'info, Table>,
+    #[account(mut, address = table.global_state)]
     pub global_state: Account<'info, GlobalState>,
     #[account(mut, address = global_state.vault_usdc)]
     pub global_vault_usdc: Account<'info, TokenAccount>,
 
+    #[account(
+        init,
+        payer = player,
+        space = 8 + BetAccount::space(call.chip_count()),
+        seeds = [b"bet", table.key().as_ref(), player.key().as_ref(), &table.bet_seq.to_le_bytes()],
+        bump
+    )]
+    pub bet: Account<'info, BetAccount>,
+
+    /// CHECK: ORAO VRF randomness account
+    #[account(
+        mut,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, &force],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    pub random: AccountInfo<'info>,
+
+    /// CHECK: treasury (placeholder)
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    /// CHECK: ORAO VRF config PDA (validated by seeds::program + seeds).
+    pub config: AccountInfo<'info>,
+
+    pub vrf: Program<'info, OraoVrfProgram>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveBet<'info> {
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+    #[account(mut)]
+    pub table: Account<'info, Table>,
+    #[account(mut, has_one = table @ RouletteError::BetTableMismatch)]
+    pub bet: Account<'info, BetAccount>,
+
+    #[account(
+        mut,
+        constraint = player_usdc_ata.owner == bet.player @ RouletteError::PlayerTokenAccountMismatch,
+        constraint = player_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch
+    )]
+    pub player_usdc_ata: Account<'info, TokenAccount>,
+    /// Receives the bet's crank tip; the player's account is used when omitted.
+    #[account(mut, constraint = resolver_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch)]
+    pub resolver_usdc_ata: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = table.global_state @ RouletteError::GlobalStateMismatch)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc @ RouletteError::VaultMismatch)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+
     /// CHECK: ORAO randomness request account
-    #[account(mut, address = bet.randomness_account)]
+    #[account(mut, address = bet.randomness_account @ RouletteError::RandomnessAccountMismatch)]
     pub random: AccountInfo<'info>,
 
     pub token_program: Program<'info, Token>,
 }
 
+/// Bets, their randomness accounts and player token accounts are passed as remaining
+/// accounts and validated by `resolve_bets`.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveBets<'info> {
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+    #[account(mut)]
+    pub table: Account<'info, Table>,
+    /// Receives the batch's crank tips; each player's account is used when omitted.
+    #[account(mut, constraint = resolver_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch)]
+    pub resolver_usdc_ata: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = table.global_state @ RouletteError::GlobalStateMismatch)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc @ RouletteError::VaultMismatch)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
 #[derive(Accounts)]
 pub struct RefundExpiredBet<'info> {
     #[account(mut)]
     pub caller: Signer<'info>,
     #[account(mut)]
     pub table: Account<'info, Table>,
+    #[account(mut, has_one = table @ RouletteError::BetTableMismatch)]
+    pub bet: Account<'info, BetAccount>,
+
+    #[account(
+        mut,
+        constraint = player_usdc_ata.owner == bet.player @ RouletteError::PlayerTokenAccountMismatch,
+        constraint = player_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch
+    )]
+    pub player_usdc_ata: Account<'info, TokenAccount>,
+    /// Receives the bet's crank tip; the player's account is used when omitted.
+    #[account(mut, constraint = resolver_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch)]
+    pub resolver_usdc_ata: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = table.global_state @ RouletteError::GlobalStateMismatch)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc @ RouletteError::VaultMismatch)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+
+    /// CHECK: ORAO randomness request account, decoded to ensure it is still unfulfilled
+    #[account(address = bet.randomness_account @ RouletteError::RandomnessAccountMismatch)]
+    pub random: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(force: [u8; 32])]
+pub struct SpinImprisonedBet<'info> {
     #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = bet.state == BetState::Imprisoned @ RouletteError::BetNotImprisoned,
+        constraint = bet.randomness_account == Pubkey::default() @ RouletteError::ImprisonedBetAlreadySpun
+    )]
     pub bet: Account<'info, BetAccount>,
+    /// Round that imprisoned the bet; required for round bets.
+    #[account(mut, address = bet.round @ RouletteError::RoundBetMismatch)]
+    pub round: Option<Account<'info, Round>>,
+
+    /// CHECK: ORAO VRF randomness account for the next spin
+    #[account(
+        mut,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, &force],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    pub random: AccountInfo<'info>,
 
+    /// CHECK: ORAO treasury, validated by the VRF program
     #[account(mut)]
-    pub player_usdc_ata: Account<'info, TokenAccount>,
+    pub treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    /// CHECK: ORAO VRF config PDA (validated by seeds::program + seeds)
+    pub config: AccountInfo<'info>,
+
+    pub vrf: Program<'info, OraoVrfProgram>,
+    pub system_program: Program<'info, System>,
+}
 
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ReleaseImprisonedBet<'info> {
+    pub caller: Signer<'info>,
     #[account(mut)]
+    pub table: Account<'info, Table>,
+    #[account(
+        mut,
+        has_one = table @ RouletteError::BetTableMismatch,
+        constraint = bet.state == BetState::Imprisoned @ RouletteError::BetNotImprisoned,
+        constraint = bet.randomness_account == Pubkey::default() @ RouletteError::ImprisonedBetAlreadySpun
+    )]
+    pub bet: Account<'info, BetAccount>,
+    /// Round that imprisoned the bet; required for round bets.
+    #[account(mut, address = bet.round @ RouletteError::RoundBetMismatch)]
+    pub round: Option<Account<'info, Round>>,
+
+    #[account(
+        mut,
+        constraint = player_usdc_ata.owner == bet.player @ RouletteError::PlayerTokenAccountMismatch,
+        constraint = player_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch
+    )]
+    pub player_usdc_ata: Account<'info, TokenAccount>,
+
+    #[account(mut, address = table.global_state @ RouletteError::GlobalStateMismatch)]
     pub global_state: Account<'info, GlobalState>,
-    #[account(mut, address = global_state.vault_usdc)]
+    #[account(mut, address = global_state.vault_usdc @ RouletteError::VaultMismatch)]
     pub global_vault_usdc: Account<'info, TokenAccount>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct TableView<'info> {
+    pub table: Account<'info, Table>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseBet<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(
+        mut,
+        has_one = player,
+        constraint = matches!(bet.state, BetState::Resolved | BetState::Refunded) @ RouletteError::BetStillPending,
+        close = player
+    )]
+    pub bet: Account<'info, BetAccount>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct OpenRound<'info> {
+    #[account(mut)]
+    pub operator: Signer<'info>,
+    #[account(mut, has_one = operator)]
+    pub table: Account<'info, Table>,
+
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + Round::SIZE,
+        seeds = [b"round", table.key().as_ref(), &table.round_seq.to_le_bytes()],
+        bump
+    )]
+    pub round: Account<'info, Round>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct PlaceRoundBet<'info> {
+    #[account(mut)]
+    pub player: Signer<'info>,
+    #[account(mut)]
+    pub player_usdc_ata: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub table: Account<'info, Table>,
+    #[account(mut, has_one = table)]
+    pub round: Account<'info, Round>,
+    #[account(mut, address = table.global_state)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = player,
+        space = 8 + BetAccount::space(1),
+        seeds = [b"bet", table.key().as_ref(), player.key().as_ref(), &table.bet_seq.to_le_bytes()],
+        bump
+    )]
+    pub bet: Account<'info, BetAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(force: [u8; 32])]
+pub struct CloseRound<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub round: Account<'info, Round>,
+
+    /// CHECK: ORAO VRF randomness account for this round's force seed
+    #[account(
+        mut,
+        seeds = [RANDOMNESS_ACCOUNT_SEED, &force],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    pub random: AccountInfo<'info>,
+
+    /// CHECK: treasury (placeholder)
+    #[account(mut)]
+    pub treasury: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [CONFIG_ACCOUNT_SEED],
+        bump,
+        seeds::program = orao_solana_vrf::ID
+    )]
+    /// CHECK: ORAO VRF config PDA (validated by seeds::program + seeds).
+    pub config: AccountInfo<'info>,
+
+    pub vrf: Program<'info, OraoVrfProgram>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleRound<'info> {
+    #[account(mut)]
+    pub round: Account<'info, Round>,
+    #[account(address = round.table @ RouletteError::RoundTableMismatch)]
+    pub table: Account<'info, Table>,
+
+    /// CHECK: ORAO randomness request account
+    #[account(address = round.randomness_account)]
+    pub random: AccountInfo<'info>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ResolveRoundBet<'info> {
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+    #[account(mut)]
+    pub table: Account<'info, Table>,
+    #[account(mut, has_one = table @ RouletteError::RoundTableMismatch)]
+    pub round: Account<'info, Round>,
+    #[account(
+        mut,
+        has_one = table @ RouletteError::BetTableMismatch,
+        has_one = round @ RouletteError::RoundBetMismatch
+    )]
+    pub bet: Account<'info, BetAccount>,
+
+    #[account(
+        mut,
+        constraint = player_usdc_ata.owner == bet.player @ RouletteError::PlayerTokenAccountMismatch,
+        constraint = player_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch
+    )]
+    pub player_usdc_ata: Account<'info, TokenAccount>,
+    /// Receives the bet's crank tip; the player's account is used when omitted.
+    #[account(mut, constraint = resolver_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch)]
+    pub resolver_usdc_ata: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = table.global_state @ RouletteError::GlobalStateMismatch)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc @ RouletteError::VaultMismatch)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundRoundBet<'info> {
+    #[account(mut)]
+    pub caller: Signer<'info>,
+    #[account(mut)]
+    pub table: Account<'info, Table>,
+    #[account(mut, has_one = table @ RouletteError::RoundTableMismatch)]
+    pub round: Account<'info, Round>,
+    #[account(
+        mut,
+        has_one = table @ RouletteError::BetTableMismatch,
+        has_one = round @ RouletteError::RoundBetMismatch
+    )]
+    pub bet: Account<'info, BetAccount>,
+
+    #[account(
+        mut,
+        constraint = player_usdc_ata.owner == bet.player @ RouletteError::PlayerTokenAccountMismatch,
+        constraint = player_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch
+    )]
+    pub player_usdc_ata: Account<'info, TokenAccount>,
+    /// Receives the bet's crank tip; the player's account is used when omitted.
+    #[account(mut, constraint = resolver_usdc_ata.mint == table.usdc_mint @ RouletteError::TokenMintMismatch)]
+    pub resolver_usdc_ata: Option<Account<'info, TokenAccount>>,
+
+    #[account(mut, address = table.global_state @ RouletteError::GlobalStateMismatch)]
+    pub global_state: Account<'info, GlobalState>,
+    #[account(mut, address = global_state.vault_usdc @ RouletteError::VaultMismatch)]
+    pub global_vault_usdc: Account<'info, TokenAccount>,
+
+    /// CHECK: ORAO randomness request account of a closed round
+    #[account(address = round.randomness_account @ RouletteError::RandomnessAccountMismatch)]
+    pub random: Option<AccountInfo<'info>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+// ORAO program wrapper (Anchor requires type)
+#[derive(Clone)]
+pub struct OraoVrfProgram;
+impl anchor_lang::Id for OraoVrfProgram {
+    fn id() -> Pubkey {
+        Pubkey::from_str("VRFzZoJdhFWL8rkvu87LpKM3RbcVezpMEc6X5GVDr7y").unwrap()
+    }
+}
+
+// -------------------- State --------------------
+
+#[account]
+#[derive(Default)]
+pub struct Table {
+    pub seed: u64,
+    pub creator: Pubkey,
+    pub operator: Pubkey,
+
+    pub mode: TableMode,
+    /// Wheel and zero rules, fixed at creation.
+    pub variant: TableVariant,
+    pub paused: bool,
+    /// Set by `retire_table`; the table only winds down from here.
+    pub retiring: bool,
+    /// GOV an operator must hold, in basis points of the GOV mint's supply.
+    pub operator_threshold_bps: u16,
+
+    pub usdc_mint: Pubkey,
+    pub gov_mint: Pubkey,
+    pub global_state: Pubkey,
+    pub control_vault_gov: Pubkey,
+
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub bet_timeout: i64,
+    pub withdraw_delay: i64,
+    /// Bitmask of `BetKind::bit()` values players may bet on.
+    pub enabled_kinds: u32,
+    /// Share of realized house profit paid out to GOV depositors, in basis points.
+    pub profit_share_bps: u16,
+    /// Operator fee on player winnings, and fees accrued but not yet claimed.
+    pub operator_fee_bps: u16,
+    pub operator_fees: u64,
+    /// USDC each bet escrows on top of its stake to pay the resolver (0 = no tip).
+    pub crank_tip: u64,
+    /// Cap on the payout owed on any single number (0 = bounded only by liquidity).
+    pub max_number_exposure: u64,
+    /// Tighter caps for individual numbers, set by `set_number_caps`.
+    pub number_caps: NumberCaps,
+    /// Cap on a single bet's worst-case payout, in basis points of the table's free
+    /// liquidity (0 = no cap).
+    pub max_payout_bps: u16,
+
+    /// Payout owed on each outcome by pending bets; the largest slot is what is locked.
+    pub exposure: Exposure,
+    pub locked_liability: u64,
+    pub active_bets: u32,
+    pub bet_seq: u64,
+
+    /// This table's share of the global vault: deposited capital plus realized P&L
+    /// plus stakes of its pending bets.
+    pub liquidity: u64,
+    pub deposited_capital: u64,
+    pub realized_pnl: i64,
+
+    /// LP share mint (default until `init_lp_vault`). `total_shares` covers both minted LP
+    /// tokens and the operator's untokenized `operator_shares`, which are paid out to the
+    /// operator when they are replaced.
+    pub lp_mint: Pubkey,
+    pub total_shares: u64,
+    pub operator_shares: u64,
+
+    pub round_seq: u64,
+    pub proposal_seq: u64,
+
+    /// Revenue sharing: USDC per deposited GOV accrued so far (scaled by
+    /// `REWARD_INDEX_SCALE`), USDC set aside for claims, the part of it already booked to
+    /// deposits but not claimed, and P&L not yet shared (negative while earlier losses are
+    /// still being made up).
+    pub total_gov_deposited: u64,
+    pub reward_index: u128,
+    pub reward_pool: u64,
+    pub rewards_owed: u64,
+    pub undistributed_pnl: i64,
+
+    pub withdraw_request_ts: i64,
+    pub withdraw_request_amount: u64,
+
+    /// Operator claim in its challenge window (default when none), with the GOV deposit
+    /// it was made with.
+    pub pending_operator: Pubkey,
+    pub pending_operator_gov: u64,
+    pub operator_claim_ts: i64,
+
+    /// Config change queued by `update_table_config` (none while `config_request_ts` is 0).
+    pub pending_config: TableConfig,
+    pub config_request_ts: i64,
+    /// Mode switch queued by `set_mode` (none while `mode_request_ts` is 0).
+    pub pending_mode: TableMode,
+    pub mode_request_ts: i64,
+    /// Caps queued by `set_number_caps` (none while `number_caps_request_ts` is 0).
+    pub pending_number_caps: NumberCaps,
+    pub number_caps_request_ts: i64,
+
+    pub bumps: TableBumps,
+}
+
+impl Table {
+    pub const SIZE: usize = 8 + 32 + 32
+        + 1 + 1 + 1 + 1 + 2
+        + 32 + 32 + 32 + 32
+        + 8 + 8 + 8 + 8 + 4 + 2
+        + 2 + 8 + 8 + 8 + NumberCaps::SIZE + 2
+        + Exposure::SIZE + 8 + 4 + 8
+        + 8 + 8 + 8
+        + 32 + 8 + 8
+        + 8 + 8
+        + 8 + 16 + 8 + 8 + 8
+        + 8 + 8
+        + 32 + 8 + 8
+        + TableConfig::SIZE + 8
+        + 1 + 8
+        + NumberCaps::SIZE + 8
+        + TableBumps::SIZE;
+
+    pub fn config(&self) -> TableConfig {
+        TableConfig {
+            min_bet: self.min_bet,
+            max_bet: self.max_bet,
+            bet_timeout: self.bet_timeout,
+            withdraw_delay: self.withdraw_delay,
+            enabled_kinds: self.enabled_kinds,
+            profit_share_bps: self.profit_share_bps,
+            operator_fee_bps: self.operator_fee_bps,
+            crank_tip: self.crank_tip,
+            max_number_exposure: self.max_number_exposure,
+            max_payout_bps: self.max_payout_bps,
+        }
+    }
+
+    pub fn apply_config(&mut self, config: &TableConfig) {
+        self.min_bet = config.min_bet;
+        self.max_bet = config.max_bet;
+        self.bet_timeout = config.bet_timeout;
+        self.withdraw_delay = config.withdraw_delay;
+        self.enabled_kinds = config.enabled_kinds;
+        self.profit_share_bps = config.profit_share_bps;
+        self.operator_fee_bps = config.operator_fee_bps;
+        self.crank_tip = config.crank_tip;
+        self.max_number_exposure = config.max_number_exposure;
+        self.max_payout_bps = config.max_payout_bps;
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct TableConfig {
+    pub min_bet: u64,
+    pub max_bet: u64,
+    pub bet_timeout: i64,
+    pub withdraw_delay: i64,
+    pub enabled_kinds: u32,
+    pub profit_share_bps: u16,
+    pub operator_fee_bps: u16,
+    pub crank_tip: u64,
+    pub max_number_exposure: u64,
+    pub max_payout_bps: u16,
+}
+impl TableConfig {
+    pub const SIZE: usize = 8 + 8 + 8 + 8 + 4 + 2 + 2 + 8 + 8 + 2;
+}
+
+/// Largest stake a single chip of one bet kind can currently carry, as reported by
+/// `bet_limits`. `kind` is the kind's `BetKind::bit()`; 0 stake means it cannot be bet.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BetLimit {
+    pub kind: u32,
+    pub max_stake: u64,
+}
+
+/// Payout owed on each pocket of the wheel by a table's pending bets (00 in slot 37).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Exposure {
+    pub payouts: [u64; 38],
+}
+impl Default for Exposure {
+    fn default() -> Self {
+        Self { payouts: [0; 38] }
+    }
+}
+impl Exposure {
+    pub const SIZE: usize = 8 * 38;
+
+    /// Worst case over every outcome.
+    pub fn max(&self) -> u64 {
+        self.payouts.iter().copied().max().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, chips: &[BetChip], variant: TableVariant, imprisoned: bool) -> Result<()> {
+        for (n, slot) in self.payouts.iter_mut().take(variant.pockets() as usize).enumerate() {
+            *slot = slot
+                .checked_add(chips_exposure(chips, n as u8, variant, imprisoned)?)
+                .ok_or(RouletteError::MathOverflow)?;
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, chips: &[BetChip], variant: TableVariant, imprisoned: bool) -> Result<()> {
+        for (n, slot) in self.payouts.iter_mut().take(variant.pockets() as usize).enumerate() {
+            *slot = slot.saturating_sub(chips_exposure(chips, n as u8, variant, imprisoned)?);
+        }
+        Ok(())
+    }
+}
+
+/// Per-number caps on the payout owed by pending bets (00 in slot 37, 0 = no cap of its own).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NumberCaps {
+    pub caps: [u64; 38],
+}
+impl Default for NumberCaps {
+    fn default() -> Self {
+        Self { caps: [0; 38] }
+    }
+}
+impl NumberCaps {
+    pub const SIZE: usize = 8 * 38;
+
+    /// Cap on number `n` once the table-wide `table_cap` is applied too (0 = none).
+    pub fn effective(&self, table_cap: u64, n: usize) -> u64 {
+        match (table_cap, self.caps[n]) {
+            (0, cap) | (cap, 0) => cap,
+            (a, b) => a.min(b),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TableVariant {
+    /// Single zero; even-money bets lose on it.
+    #[default]
+    European,
+    /// 0 and 00; even-money bets lose on both.
+    American,
+    /// French single zero with La Partage: even-money bets get half their stake back on 0.
+    LaPartage,
+    /// French single zero with En Prison: even-money bets on 0 are imprisoned and ride the
+    /// next spin, returning only their stake if it wins.
+    EnPrison,
+}
+impl TableVariant {
+    pub fn pockets(self) -> u8 {
+        match self {
+            TableVariant::American => 38,
+            _ => 37,
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum TableMode {
+    #[default]
+    Private = 0,
+    Public = 1,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct TableBumps {
+    pub table: u8,
+    pub control_vault_gov: u8,
+}
+impl TableBumps {
+    pub const SIZE: usize = 1 + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct GlobalBumps {
+    pub global: u8,
+    pub vault_usdc: u8,
+}
+impl GlobalBumps {
+    pub const SIZE: usize = 1 + 1;
+}
+
+#[account]
+pub struct GlobalState {
+    pub usdc_mint: Pubkey,
+    pub vault_usdc: Pubkey,
+    pub total_locked_liability: u64,
+    pub total_active_bets: u64,
+
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub pauser: Pubkey,
+    pub fee_manager: Pubkey,
+
+    /// Global circuit breaker: blocks new bets on every table of this mint.
+    pub paused: bool,
+    /// Free vault liquidity under which the breaker trips automatically (0 = disabled).
+    pub liquidity_floor: u64,
+
+    /// Protocol fee on player winnings, and fees accrued but not yet claimed.
+    pub protocol_fee_bps: u16,
+    pub protocol_fees: u64,
+
+    pub bumps: GlobalBumps,
+}
+impl GlobalState {
+    pub const SIZE: usize = 32 + 32 + 8 + 8
+        + 32 + 32 + 32 + 32
+        + 1 + 8
+        + 2 + 8
+        + GlobalBumps::SIZE;
+
+    /// The admin implicitly holds every role. The pauser acts as the guardian of the
+    /// global circuit breaker.
+    pub fn has_role(&self, role: GlobalRole, key: &Pubkey) -> bool {
+        if *key == self.admin {
+            return true;
+        }
+        let holder = match role {
+            GlobalRole::Pauser => self.pauser,
+            GlobalRole::FeeManager => self.fee_manager,
+        };
+        holder != Pubkey::default() && holder == *key
+    }
+}
+
+/// `GlobalState` as first deployed, before roles, fees and the circuit breaker.
+#[derive(AnchorDeserialize, Default)]
+struct GlobalStateV1 {
+    usdc_mint: Pubkey,
+    vault_usdc: Pubkey,
+    total_locked_liability: u64,
+    total_active_bets: u64,
+}
+
+/// Decodes a `global_state` account's data under the current layout, or failing that the
+/// fields of the original one, for `repair_global` to carry over. Anything that does not
+/// decode comes back zeroed.
+fn decode_previous_global(data: &[u8]) -> GlobalState {
+    let body = if data.len() >= 8 && data[..8] == anchor_account_discriminator("GlobalState") {
+        &data[8..]
+    } else {
+        &[]
+    };
+    if let Ok(gs) = GlobalState::deserialize(&mut &body[..]) {
+        return gs;
+    }
+    let v1 = GlobalStateV1::deserialize(&mut &body[..]).unwrap_or_default();
+    GlobalState {
+        usdc_mint: v1.usdc_mint,
+        vault_usdc: v1.vault_usdc,
+        total_locked_liability: v1.total_locked_liability,
+        total_active_bets: v1.total_active_bets,
+        admin: Pubkey::default(),
+        pending_admin: Pubkey::default(),
+        pauser: Pubkey::default(),
+        fee_manager: Pubkey::default(),
+        paused: false,
+        liquidity_floor: 0,
+        protocol_fee_bps: 0,
+        protocol_fees: 0,
+        bumps: GlobalBumps { global: 0, vault_usdc: 0 },
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum GlobalRole {
+    Pauser = 0,
+    FeeManager = 1,
+}
+
+#[account]
+#[derive(Default)]
+pub struct GovDeposit {
+    pub table: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    /// End of the latest vote this deposit took part in; withdrawals wait until then.
+    pub vote_lock_until: i64,
+
+    /// Voluntary lockup set by `lock_gov`; voting weight is boosted until it ends.
+    pub lock_until: i64,
+    pub lock_boost_bps: u16,
+    /// Key holding this deposit's control and voting power (default = the depositor).
+    pub delegate: Pubkey,
+
+    /// `Table::reward_index` when this deposit was last synced, and rewards booked since.
+    pub reward_index: u128,
+    pub pending_rewards: u64,
+}
+impl GovDeposit {
+    pub const SIZE: usize = 32 + 32 + 8 + 8
+        + 8 + 2
+        + 32
+        + 16 + 8;
+}
+
+#[account]
+pub struct Proposal {
+    pub table: Pubkey,
+    pub proposer: Pubkey,
+    pub id: u64,
+    pub action: ProposalAction,
+    pub created_ts: i64,
+    pub voting_ends_ts: i64,
+    /// Deposited GOV that must vote, fixed from the GOV supply when the proposal is created.
+    pub quorum: u64,
+    /// Lockup-boosted weight for and against.
+    pub yes_votes: u64,
+    pub no_votes: u64,
+    /// Deposited GOV behind the votes cast, unboosted, measured against `quorum`.
+    pub turnout: u64,
+    pub executed: bool,
+}
+impl Proposal {
+    pub const SIZE: usize = 32 + 32 + 8
+        + ProposalAction::MAX_SIZE
+        + 8 + 8
+        + 8 + 8 + 8 + 8
+        + 1;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub enum ProposalAction {
+    SetOperator { operator: Pubkey },
+    SetMode { mode: TableMode },
+    SetConfig { config: TableConfig },
+    SetPaused { paused: bool },
+    WithdrawLiquidity { amount: u64 },
+}
+impl ProposalAction {
+    pub const MAX_SIZE: usize = 1 + TableConfig::SIZE;
+}
+
+#[account]
+pub struct VoteRecord {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub weight: u64,
+    pub support: bool,
+}
+impl VoteRecord {
+    pub const SIZE: usize = 32 + 32 + 8 + 1;
+}
+
+#[account]
+pub struct LpRedeemRequest {
+    pub table: Pubkey,
+    pub provider: Pubkey,
+    pub shares: u64,
+    pub request_ts: i64,
+}
+impl LpRedeemRequest {
+    pub const SIZE: usize = 32 + 32 + 8 + 8;
+}
+
+#[account]
+pub struct BetAccount {
+    pub table: Pubkey,
+    pub player: Pubkey,
+
+    /// Sum of all chip stakes.
+    pub stake: u64,
+    /// Worst-case total payout of this bet alone; the table locks its combined exposure.
+    pub max_total_payout: u64,
+
+    pub chips: Vec<BetChip>,
+    pub state: BetState,
+
+    pub created_ts: i64,
+    /// Refund timeout in force when the bet was placed; later config changes don't move it.
+    pub bet_timeout: i64,
+    pub force: [u8; 32],
+
+    pub randomness_account: Pubkey,
+    pub result_number: Option<u8>,
+
+    /// Shared-spin round this bet belongs to (default for single-spin bets). A round bet
+    /// imprisoned by the round's zero keeps it until it is spun or released.
+    pub round: Pubkey,
+
+    /// Crank tip escrowed with the stake, paid to whoever settles or refunds the bet.
+    pub tip: u64,
+}
+impl BetAccount {
+    pub const MAX_CHIPS: usize = 16;
+
+    pub const fn space(chips: usize) -> usize {
+        32 + 32
+            + 8 + 8
+            + 4 + chips * BetChip::SIZE + 1
+            + 8 + 8 + 32
+            + 32 + 2
+            + 32
+            + 8
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BetChip {
+    pub kind: BetKind,
+    pub stake: u64,
+}
+impl BetChip {
+    pub const SIZE: usize = BetKind::MAX_SIZE + 8;
+}
+
+#[account]
+pub struct Round {
+    pub table: Pubkey,
+    pub round_id: u64,
+    pub state: RoundState,
+
+    pub opened_ts: i64,
+    pub closes_ts: i64,
+    /// Refund timeout in force when the round opened.
+    pub bet_timeout: i64,
+
+    pub force: [u8; 32],
+    pub randomness_account: Pubkey,
+    pub result_number: Option<u8>,
+
+    pub bet_count: u32,
+    /// Bets still waiting on this round's outcome.
+    pub open_bets: u32,
+    /// Bets the round's zero sent to prison that are not yet spun or released.
+    pub imprisoned_bets: u32,
+}
+impl Round {
+    pub const SIZE: usize = 32 + 8 + 1
+        + 8 + 8 + 8
+        + 32 + 32 + 2
+        + 4 + 4 + 4;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum RoundState {
+    Open = 0,
+    Closed = 1,
+    Settled = 2,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+pub enum BetState {
+    Pending = 0,
+    Resolved = 1,
+    Refunded = 2,
+    /// En Prison: the bet's even-money chips hit zero and wait for the next spin.
+    Imprisoned = 3,
+}
+
+/// Racetrack calls on the single-zero wheel, expanded into regular chips when placed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AnnouncedBet {
+    /// 0-2-3 trio and 25-29 corner at two units, splits 4/7, 12/15, 18/21, 19/22, 32/35.
+    VoisinsDuZero,
+    /// Tiers du Cylindre: splits 5/8, 10/11, 13/16, 23/24, 27/30, 33/36.
+    Tiers,
+    /// Orphelins: straight 1, splits 6/9, 14/17, 17/20, 31/34.
+    Orphelins,
+    /// `number` plus `neighbours` pockets on each side in wheel order, as straights.
+    Neighbours { number: u8, neighbours: u8 },
+}
+impl AnnouncedBet {
+    /// Chips the call expands to, capped at `BetAccount::MAX_CHIPS` for account sizing.
+    pub fn chip_count(&self) -> usize {
+        match self {
+            AnnouncedBet::VoisinsDuZero => 7,
+            AnnouncedBet::Tiers => 6,
+            AnnouncedBet::Orphelins => 5,
+            AnnouncedBet::Neighbours { neighbours, .. } => {
+                (2 * *neighbours as usize + 1).min(BetAccount::MAX_CHIPS)
+            }
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum BetKind {
+    Straight { number: u8 },
+    Split { a: u8, b: u8 },
+    Street { row: u8 },
+    Corner { row: u8, col: u8 },
+    SixLine { row: u8 },
+    Red,
+    Black,
+    Even,
+    Odd,
+    Low,
+    High,
+    Dozen { idx: u8 },
+    Column { idx: u8 },
+    /// Three numbers including a zero: 0-1-2 or 0-2-3 (0-1-2, 0-00-2 or 00-2-3 on American).
+    Trio { a: u8, b: u8, c: u8 },
+    /// First four, 0-1-2-3 (single-zero wheels).
+    Basket,
+    /// Top line, 0-00-1-2-3 (American wheel).
+    TopLine,
+}
+impl BetKind {
+    pub const MAX_SIZE: usize = 1 + 3;
+    /// Every kind enabled.
+    pub const ALL: u32 = (1 << 16) - 1;
+
+    /// Bit of this kind in `Table::enabled_kinds`, in declaration order.
+    pub fn bit(&self) -> u32 {
+        let idx = match self {
+            BetKind::Straight { .. } => 0,
+            BetKind::Split { .. } => 1,
+            BetKind::Street { .. } => 2,
+            BetKind::Corner { .. } => 3,
+            BetKind::SixLine { .. } => 4,
+            BetKind::Red => 5,
+            BetKind::Black => 6,
+            BetKind::Even => 7,
+            BetKind::Odd => 8,
+            BetKind::Low => 9,
+            BetKind::High => 10,
+            BetKind::Dozen { .. } => 11,
+            BetKind::Column { .. } => 12,
+            BetKind::Trio { .. } => 13,
+            BetKind::Basket => 14,
+            BetKind::TopLine => 15,
+        };
+        1 << idx
+    }
+}
+
+// -------------------- Events --------------------
+
+#[event]
+pub struct TableCreated {
+    pub table: Pubkey,
+    pub creator: Pubkey,
+    pub global_state: Pubkey,
+    pub gov_mint: Pubkey,
+    pub mode: TableMode,
+    pub variant: TableVariant,
+    pub min_bet: u64,
+    pub max_bet: u64,
+}
+
+#[event]
+pub struct ProposalCreated {
+    pub table: Pubkey,
+    pub proposal: Pubkey,
+    pub proposer: Pubkey,
+    pub id: u64,
+    pub action: ProposalAction,
+    pub voting_ends_ts: i64,
+}
+
+#[event]
+pub struct VoteCast {
+    pub proposal: Pubkey,
+    pub voter: Pubkey,
+    pub support: bool,
+    pub weight: u64,
+}
+
+#[event]
+pub struct ProposalExecuted {
+    pub table: Pubkey,
+    pub proposal: Pubkey,
+    pub action: ProposalAction,
+}
+
+#[event]
+pub struct TableRetiring {
+    pub table: Pubkey,
+}
+
+#[event]
+pub struct TableClosed {
+    pub table: Pubkey,
+    pub creator: Pubkey,
+    pub swept_liquidity: u64,
+    pub swept_gov: u64,
+}
+
+#[event]
+pub struct ModeChanged {
+    pub table: Pubkey,
+    pub mode: TableMode,
+}
+
+#[event]
+pub struct ModeChangeQueued {
+    pub table: Pubkey,
+    pub mode: TableMode,
+    pub eta: i64,
+}
+
+#[event]
+pub struct Paused {
+    pub table: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct TableConfigQueued {
+    pub table: Pubkey,
+    pub config: TableConfig,
+    pub eta: i64,
+}
+
+#[event]
+pub struct TableConfigUpdated {
+    pub table: Pubkey,
+    pub config: TableConfig,
+}
+
+#[event]
+pub struct NumberCapsQueued {
+    pub table: Pubkey,
+    pub caps: NumberCaps,
+    pub eta: i64,
+}
+
+#[event]
+pub struct NumberCapsUpdated {
+    pub table: Pubkey,
+    pub caps: NumberCaps,
+}
+
+#[event]
+pub struct ProtocolFeeSet {
+    pub global_state: Pubkey,
+    pub fee_bps: u16,
+}
+
+#[event]
+pub struct FeesClaimed {
+    pub global_state: Pubkey,
+    /// Default for protocol fees.
+    pub table: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct GlobalPaused {
+    pub global_state: Pubkey,
+    pub paused: bool,
+    /// True when tripped by the liquidity floor rather than by the pauser.
+    pub breaker_tripped: bool,
+}
+
+#[event]
+pub struct LiquidityFloorSet {
+    pub global_state: Pubkey,
+    pub floor: u64,
+}
+
+#[event]
+pub struct AdminTransferStarted {
+    pub global_state: Pubkey,
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[event]
+pub struct AdminTransferred {
+    pub global_state: Pubkey,
+    pub previous: Pubkey,
+    pub admin: Pubkey,
+}
+
+#[event]
+pub struct RoleSet {
+    pub global_state: Pubkey,
+    pub role: GlobalRole,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct GovDeposited {
+    pub table: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub total: u64,
+}
+
+#[event]
+pub struct GovWithdrawn {
+    pub table: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub total: u64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub table: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct GovLocked {
+    pub table: Pubkey,
+    pub depositor: Pubkey,
+    pub lock_until: i64,
+    pub boost_bps: u16,
+}
+
+#[event]
+pub struct GovDelegated {
+    pub table: Pubkey,
+    pub depositor: Pubkey,
+    pub delegate: Pubkey,
+}
+
+#[event]
+pub struct GovDepositClosed {
+    pub table: Pubkey,
+    pub depositor: Pubkey,
+}
+
+#[event]
+pub struct OperatorClaimStarted {
+    pub table: Pubkey,
+    pub claimant: Pubkey,
+    pub gov_amount: u64,
+    pub challenge_ends_ts: i64,
+}
+
+#[event]
+pub struct OperatorClaimChallenged {
+    pub table: Pubkey,
+    pub claimant: Pubkey,
+    pub operator: Pubkey,
+    pub operator_gov: u64,
+}
+
+#[event]
+pub struct OperatorClaimed {
+    pub table: Pubkey,
+    pub previous: Pubkey,
+    pub operator: Pubkey,
+    pub gov_amount: u64,
+}
+
+#[event]
+pub struct LiquidityDeposited {
+    pub table: Pubkey,
+    pub provider: Pubkey,
+    pub amount: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct WithdrawRequested {
+    pub table: Pubkey,
+    pub amount: u64,
+    pub request_ts: i64,
+}
+
+#[event]
+pub struct WithdrawExecuted {
+    pub table: Pubkey,
+    pub operator: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct OperatorPositionSettled {
+    pub table: Pubkey,
+    pub operator: Pubkey,
+    pub shares: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LpVaultInitialized {
+    pub table: Pubkey,
+    pub lp_mint: Pubkey,
+}
+
+#[event]
+pub struct LpRedeemRequested {
+    pub table: Pubkey,
+    pub provider: Pubkey,
+    pub shares: u64,
+    pub request_ts: i64,
+}
+
+#[event]
+pub struct LpRedeemed {
+    pub table: Pubkey,
+    pub provider: Pubkey,
+    pub shares: u64,
+    pub amount: u64,
+}
+
+#[event]
+pub struct BetPlaced {
+    pub table: Pubkey,
+    pub bet: Pubkey,
+    pub player: Pubkey,
+    pub stake: u64,
+    pub max_total_payout: u64,
+    pub tip: u64,
+    pub chips: Vec<BetChip>,
+    pub round: Pubkey,
+    pub randomness_account: Pubkey,
+}
+
+#[event]
+pub struct BetResolved {
+    pub table: Pubkey,
+    pub bet: Pubkey,
+    pub player: Pubkey,
+    pub result_number: u8,
+    /// Paid to the player, net of fees.
+    pub payout: u64,
+    pub protocol_fee: u64,
+    pub operator_fee: u64,
+    pub tip: u64,
+    /// Stake of even-money chips sent to prison (En Prison tables only).
+    pub imprisoned_stake: u64,
+}
+
+#[event]
+pub struct ImprisonedBetSpun {
+    pub table: Pubkey,
+    pub bet: Pubkey,
+    pub randomness_account: Pubkey,
+}
+
+#[event]
+pub struct ImprisonedBetReleased {
+    pub table: Pubkey,
+    pub bet: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct BetRefunded {
+    pub table: Pubkey,
+    pub bet: Pubkey,
+    pub player: Pubkey,
+    pub amount: u64,
+    pub tip: u64,
+}
+
+#[event]
+pub struct BetClosed {
+    pub table: Pubkey,
+    pub bet: Pubkey,
+    pub player: Pubkey,
+}
+
+#[event]
+pub struct RoundOpened {
+    pub table: Pubkey,
+    pub round: Pubkey,
+    pub round_id: u64,
+    pub closes_ts: i64,
+}
+
+#[event]
+pub struct RoundClosed {
+    pub table: Pubkey,
+    pub round: Pubkey,
+    pub randomness_account: Pubkey,
+}
+
+#[event]
+pub struct RoundSettled {
+    pub table: Pubkey,
+    pub round: Pubkey,
+    pub result_number: u8,
+}
+
+fn bet_placed_event(bet: &BetAccount, key: Pubkey) -> BetPlaced {
+    BetPlaced {
+        table: bet.table,
+        bet: key,
+        player: bet.player,
+        stake: bet.stake,
+        max_total_payout: bet.max_total_payout,
+        tip: bet.tip,
+        chips: bet.chips.clone(),
+        round: bet.round,
+        randomness_account: bet.randomness_account,
+    }
+}
+
+fn bet_resolved_event(bet: &BetAccount, key: Pubkey, result_number: u8, settlement: &Settlement) -> BetResolved {
+    BetResolved {
+        table: bet.table,
+        bet: key,
+        player: bet.player,
+        result_number,
+        payout: settlement.amount,
+        protocol_fee: settlement.fees.protocol,
+        operator_fee: settlement.fees.operator,
+        tip: settlement.tip,
+        imprisoned_stake: settlement.imprisoned_stake,
+    }
+}
+
+fn bet_refunded_event(bet: &BetAccount, key: Pubkey) -> BetRefunded {
+    BetRefunded {
+        table: bet.table,
+        bet: key,
+        player: bet.player,
+        amount: bet.stake,
+        tip: bet.tip,
+    }
+}
+
+fn breaker_tripped_event(global_state: Pubkey) -> GlobalPaused {
+    GlobalPaused {
+        global_state,
+        paused: true,
+        breaker_tripped: true,
+    }
+}
+
+// -------------------- Helpers --------------------
+
+#[macro_export]
+macro_rules! table_signer_seeds {
+    ($table:expr) => {{
+        &[ &[ b"table", $table.creator.as_ref(), &$table.seed.to_le_bytes(), &[$table.bumps.table] ] ]
+    }};
+}
+
+/// Liquidity of a table that is not reserved for the worst-case payout of its pending bets.
+fn table_available_liquidity(table: &Table) -> u64 {
+    table.liquidity.saturating_sub(table.locked_liability)
+}
+
+/// Largest worst-case payout a single bet may carry: the risk policy's share of free
+/// liquidity, or all of it when no policy is set.
+fn risk_payout_limit(table: &Table) -> u64 {
+    let available = table_available_liquidity(table);
+    if table.max_payout_bps == 0 {
+        return available;
+    }
+    (available as u128 * table.max_payout_bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+/// One kind per `BetKind::bit()`, in bit order; the fields only pick a valid placement.
+const BET_KIND_SAMPLES: [BetKind; 16] = [
+    BetKind::Straight { number: 1 },
+    BetKind::Split { a: 1, b: 2 },
+    BetKind::Street { row: 1 },
+    BetKind::Corner { row: 1, col: 1 },
+    BetKind::SixLine { row: 1 },
+    BetKind::Red,
+    BetKind::Black,
+    BetKind::Even,
+    BetKind::Odd,
+    BetKind::Low,
+    BetKind::High,
+    BetKind::Dozen { idx: 1 },
+    BetKind::Column { idx: 1 },
+    BetKind::Trio { a: 0, b: 1, c: 2 },
+    BetKind::Basket,
+    BetKind::TopLine,
+];
+
+/// Per-number caps are not tied to a kind's sample bet, so the tightest one on the wheel
+/// bounds every kind's limit.
+fn bet_limits_for(table: &Table) -> Vec<BetLimit> {
+    let tightest_cap = (0..table.variant.pockets() as usize)
+        .map(|n| table.number_caps.effective(table.max_number_exposure, n))
+        .filter(|&cap| cap > 0)
+        .min();
+    let payout_limit = match tightest_cap {
+        None => risk_payout_limit(table),
+        Some(cap) => risk_payout_limit(table).min(cap),
+    };
+    BET_KIND_SAMPLES
+        .iter()
+        .map(|kind| {
+            let max_stake = table.max_bet.min(payout_limit / (bet_multiplier(kind) as u64 + 1));
+            let open = table.enabled_kinds & kind.bit() != 0
+                && validate_bet_kind(kind, table.variant).is_ok()
+                && !table.paused
+                && !table.retiring;
+            BetLimit {
+                kind: kind.bit(),
+                max_stake: if open && max_stake >= table.min_bet { max_stake } else { 0 },
+            }
+        })
+        .collect()
+}
+
+/// Table exposure once `chips` are added. The worst outcome must stay within `liquidity`,
+/// and every number the bet pays on within its cap: the table-wide `cap` or its own entry
+/// in `caps`, whichever is tighter (0 = no cap).
+fn exposure_after(
+    current: &Exposure,
+    chips: &[BetChip],
+    variant: TableVariant,
+    liquidity: u64,
+    cap: u64,
+    caps: &NumberCaps,
+) -> Result<Exposure> {
+    let mut exposure = *current;
+    exposure.add(chips, variant, false)?;
+    require!(exposure.max() <= liquidity, RouletteError::InsufficientLiquidity);
+    for (n, slot) in exposure.payouts.iter().enumerate() {
+        let cap = caps.effective(cap, n);
+        require!(
+            cap == 0 || *slot <= cap || *slot == current.payouts[n],
+            RouletteError::NumberExposureCapExceeded
+        );
+    }
+    Ok(exposure)
+}
+
+/// Whether `next` lowers or introduces a cap on any number compared to `current`.
+fn number_caps_tightened(current: &NumberCaps, next: &NumberCaps) -> bool {
+    current
+        .caps
+        .iter()
+        .zip(next.caps.iter())
+        .any(|(&cur, &new)| new != 0 && (cur == 0 || new < cur))
+}
+
+/// Validates a new bet against the table, adds it to the table's exposure and fills the bet
+/// account. Returns the amount to escrow, the total stake plus the table's crank tip; the
+/// caller moves it into the vault and arranges the randomness.
+fn open_bet(
+    table: &mut Account<Table>,
+    gs: &mut Account<GlobalState>,
+    bet_acc: &mut Account<BetAccount>,
+    player: Pubkey,
+    chips: Vec<BetChip>,
+    vault_balance: u64,
+    now: i64,
+) -> Result<u64> {
+    require!(!gs.paused, RouletteError::GloballyPaused);
+    require!(!table.paused, RouletteError::Paused);
+    require!(!table.retiring, RouletteError::TableRetiring);
+    require!(
+        !chips.is_empty() && chips.len() <= BetAccount::MAX_CHIPS,
+        RouletteError::InvalidChipCount
+    );
+
+    let mut stake: u64 = 0;
+    for chip in chips.iter() {
+        require!(
+            chip.stake >= table.min_bet && chip.stake <= table.max_bet,
+            RouletteError::InvalidStake
+        );
+        require!(
+            table.enabled_kinds & chip.kind.bit() != 0,
+            RouletteError::BetKindDisabled
+        );
+        validate_bet_kind(&chip.kind, table.variant)?;
+        stake = stake.checked_add(chip.stake).ok_or(RouletteError::MathOverflow)?;
+    }
+
+    // Worst case over every outcome, not the sum of each chip's own maximum
+    let max_total_payout = chips_max_payout(&chips, table.variant)?;
+    if table.max_payout_bps > 0 {
+        require!(
+            max_total_payout <= risk_payout_limit(table),
+            RouletteError::BetExceedsRiskLimit
+        );
+    }
+
+    // Opposing bets offset each other: only the worst outcome across all pending bets
+    // is locked, bounded by this table's own ledger rather than the shared vault total
+    let exposure = exposure_after(
+        &table.exposure,
+        &chips,
+        table.variant,
+        table.liquidity,
+        table.max_number_exposure,
+        &table.number_caps,
+    )?;
+    let added_liability = exposure.max().saturating_sub(table.locked_liability);
+
+    // Never admit a bet that would take the shared vault below the circuit-breaker floor
+    let free_after = vault_balance
+        .saturating_add(stake)
+        .saturating_sub(gs.total_locked_liability.saturating_add(added_liability));
+    require!(
+        gs.liquidity_floor == 0 || free_after >= gs.liquidity_floor,
+        RouletteError::BelowLiquidityFloor
+    );
+
+    // The escrowed stake sits in the vault on behalf of this table until settlement
+    table.liquidity = table.liquidity.checked_add(stake).ok_or(RouletteError::MathOverflow)?;
+    table.exposure = exposure;
+    table.locked_liability = table.locked_liability.saturating_add(added_liability);
+    table.active_bets = table.active_bets.saturating_add(1);
+    table.bet_seq = table.bet_seq.saturating_add(1);
+
+    gs.total_locked_liability = gs.total_locked_liability.saturating_add(added_liability);
+    gs.total_active_bets = gs.total_active_bets.saturating_add(1);
+
+    bet_acc.table = table.key();
+    bet_acc.player = player;
+    bet_acc.stake = stake;
+    bet_acc.max_total_payout = max_total_payout;
+    bet_acc.chips = chips;
+    bet_acc.state = BetState::Pending;
+    bet_acc.created_ts = now;
+    bet_acc.bet_timeout = table.bet_timeout;
+    bet_acc.force = [0u8; 32];
+    bet_acc.randomness_account = Pubkey::default();
+    bet_acc.result_number = None;
+    bet_acc.round = Pubkey::default();
+    // The tip is escrowed next to the stake but is never part of the table's liquidity
+    bet_acc.tip = table.crank_tip;
+
+    stake.checked_add(table.crank_tip).ok_or(error!(RouletteError::MathOverflow))
+}
+
+/// Moves a freshly opened bet's escrow from the player into the vault and requests its
+/// spin from ORAO, recording the randomness account on the bet.
+#[allow(clippy::too_many_arguments)]
+fn escrow_and_request_spin<'info>(
+    bet_acc: &mut Account<'info, BetAccount>,
+    player: &Signer<'info>,
+    player_usdc_ata: &Account<'info, TokenAccount>,
+    global_vault_usdc: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    escrow: u64,
+    random: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    config: &AccountInfo<'info>,
+    vrf: &Program<'info, OraoVrfProgram>,
+    system_program: &Program<'info, System>,
+    force: [u8; 32],
+) -> Result<()> {
+    let cpi_accounts = Transfer {
+        from: player_usdc_ata.to_account_info(),
+        to: global_vault_usdc.to_account_info(),
+        authority: player.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+    token::transfer(cpi_ctx, escrow)?;
+
+    bet_acc.force = force;
+    bet_acc.randomness_account = random.key();
+
+    // ORAO VRF CPI: request randomness
+    let cpi_accounts = orao_cpi::accounts::RequestV2 {
+        payer: player.to_account_info(),
+        network_state: config.clone(),
+        treasury: treasury.clone(),
+        request: random.clone(),
+        system_program: system_program.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(vrf.to_account_info(), cpi_accounts);
+    orao_cpi::request_v2(cpi_ctx, force)
+}
+
+/// Amount paid to the player by `settle_bet`/`refund_bet`, fees withheld from it, the
+/// crank tip paid, and whether the global circuit breaker tripped as a result.
+struct Settlement {
+    amount: u64,
+    fees: Fees,
+    tip: u64,
+    /// Stake left on the table in prison; the bet stays active when non-zero.
+    imprisoned_stake: u64,
+    breaker_tripped: bool,
+}
+
+#[derive(Default, Debug, PartialEq, Eq)]
+struct Fees {
+    protocol: u64,
+    operator: u64,
+}
+
+/// Fees on a bet's net winnings (payout above the total stake); nothing when it lost or
+/// broke even.
+fn winnings_fees(stake: u64, payout: u64, protocol_fee_bps: u16, operator_fee_bps: u16) -> Result<Fees> {
+    let winnings = payout.saturating_sub(stake);
+    let fee = |bps: u16| -> Result<u64> {
+        Ok(winnings
+            .checked_mul(bps as u64)
+            .ok_or(RouletteError::MathOverflow)?
+            / BPS_DENOMINATOR)
+    };
+    Ok(Fees {
+        protocol: fee(protocol_fee_bps)?,
+        operator: fee(operator_fee_bps)?,
+    })
+}
+
+/// Pays out a pending bet for outcome `n` and releases its locked liability.
+#[allow(clippy::too_many_arguments)]
+fn settle_bet<'info>(
+    table: &mut Account<'info, Table>,
+    gs: &mut Account<'info, GlobalState>,
+    bet: &mut Account<'info, BetAccount>,
+    global_vault_usdc: &Account<'info, TokenAccount>,
+    player_usdc_ata: &Account<'info, TokenAccount>,
+    tip_usdc_ata: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    n: u8,
+) -> Result<Settlement> {
+    let mut settlement = pay_out_bet(
+        table,
+        gs,
+        bet,
+        global_vault_usdc,
+        player_usdc_ata,
+        tip_usdc_ata,
+        token_program,
+        n,
+    )?;
+    release_liability(table, gs, u32::from(settlement.imprisoned_stake == 0));
+    settlement.breaker_tripped = trip_breaker_if_below_floor(
+        gs,
+        global_vault_usdc.amount.saturating_sub(settlement.amount).saturating_sub(settlement.tip),
+    );
+    Ok(settlement)
+}
+
+/// Pays out a pending bet for outcome `n` and books fees and house PnL, leaving the
+/// liability counters and the breaker to the caller so batches can update them once.
+/// On an En Prison table, even-money chips hitting zero stay on the bet as `Imprisoned`.
+#[allow(clippy::too_many_arguments)]
+fn pay_out_bet<'info>(
+    table: &mut Account<'info, Table>,
+    gs: &mut Account<'info, GlobalState>,
+    bet: &mut Account<'info, BetAccount>,
+    global_vault_usdc: &Account<'info, TokenAccount>,
+    player_usdc_ata: &Account<'info, TokenAccount>,
+    tip_usdc_ata: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    n: u8,
+) -> Result<Settlement> {
+    let variant = table.variant;
+    let imprisoned = bet.state == BetState::Imprisoned;
+    let total_payout = chips_payout(&bet.chips, n, variant, imprisoned)?;
+    let prisoners: Vec<BetChip> = bet
+        .chips
+        .iter()
+        .filter(|chip| goes_to_prison(chip, n, variant, imprisoned))
+        .cloned()
+        .collect();
+    let prison_stake: u64 = prisoners.iter().map(|chip| chip.stake).sum();
+    let settled_stake = bet.stake.saturating_sub(prison_stake);
+
+    // Fees come out of the player's winnings and stay in the vault on the fee ledgers
+    let fees = winnings_fees(settled_stake, total_payout, gs.protocol_fee_bps, table.operator_fee_bps)?;
+    let net_payout = total_payout - fees.protocol - fees.operator;
+
+    if net_payout > 0 {
+        transfer_from_vault(gs, global_vault_usdc, player_usdc_ata, token_program, net_payout)?;
+    }
+    pay_crank_tip(gs, global_vault_usdc, tip_usdc_ata, token_program, bet.tip)?;
+    gs.protocol_fees = gs.protocol_fees.checked_add(fees.protocol).ok_or(RouletteError::MathOverflow)?;
+    table.operator_fees = table.operator_fees.checked_add(fees.operator).ok_or(RouletteError::MathOverflow)?;
+
+    table.liquidity = table.liquidity.saturating_sub(total_payout);
+    let pnl = (settled_stake as i64).saturating_sub(total_payout as i64);
+    table.realized_pnl = table.realized_pnl.saturating_add(pnl);
+    distribute_house_pnl(table, pnl)?;
+    table.exposure.remove(&bet.chips, variant, imprisoned)?;
+
+    let tip = bet.tip;
+    bet.result_number = Some(n);
+    if prisoners.is_empty() {
+        bet.state = BetState::Resolved;
+    } else {
+        // The imprisoned chips' stake stays escrowed and rides the bet's own next spin
+        table.exposure.add(&prisoners, variant, true)?;
+        bet.chips = prisoners;
+        bet.stake = prison_stake;
+        bet.max_total_payout = prison_stake;
+        bet.state = BetState::Imprisoned;
+        bet.randomness_account = Pubkey::default();
+        bet.tip = 0;
+        // The release timeout for an unspun prisoner runs from here
+        bet.created_ts = Clock::get()?.unix_timestamp;
+    }
+
+    Ok(Settlement {
+        amount: net_payout,
+        fees,
+        tip,
+        imprisoned_stake: prison_stake,
+        breaker_tripped: false,
+    })
+}
+
+/// Re-locks the table's worst outcome after `bets` settled bets left its exposure, and
+/// frees their active-bet slots.
+fn release_liability(table: &mut Table, gs: &mut GlobalState, bets: u32) {
+    let locked = table.exposure.max();
+    let released = table.locked_liability.saturating_sub(locked);
+    table.locked_liability = locked;
+    table.active_bets = table.active_bets.saturating_sub(bets);
+    gs.total_locked_liability = gs.total_locked_liability.saturating_sub(released);
+    gs.total_active_bets = gs.total_active_bets.saturating_sub(bets as u64);
+}
+
+/// Returns a pending bet's stake and releases its locked liability.
+fn refund_bet<'info>(
+    table: &mut Account<'info, Table>,
+    gs: &mut Account<'info, GlobalState>,
+    bet: &mut Account<'info, BetAccount>,
+    global_vault_usdc: &Account<'info, TokenAccount>,
+    player_usdc_ata: &Account<'info, TokenAccount>,
+    tip_usdc_ata: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<Settlement> {
+    transfer_from_vault(gs, global_vault_usdc, player_usdc_ata, token_program, bet.stake)?;
+    pay_crank_tip(gs, global_vault_usdc, tip_usdc_ata, token_program, bet.tip)?;
+
+    table.liquidity = table.liquidity.saturating_sub(bet.stake);
+    let (variant, imprisoned) = (table.variant, bet.state == BetState::Imprisoned);
+    table.exposure.remove(&bet.chips, variant, imprisoned)?;
+    release_liability(table, gs, 1);
+    let breaker_tripped = trip_breaker_if_below_floor(
+        gs,
+        global_vault_usdc.amount.saturating_sub(bet.stake).saturating_sub(bet.tip),
+    );
+
+    bet.state = BetState::Refunded;
+
+    Ok(Settlement {
+        amount: bet.stake,
+        fees: Fees::default(),
+        tip: bet.tip,
+        imprisoned_stake: 0,
+        breaker_tripped,
+    })
+}
+
+/// Settles an unspun imprisoned bet: half of each chip's stake back to the player, the rest
+/// booked as house profit.
+fn release_prisoner<'info>(
+    table: &mut Account<'info, Table>,
+    gs: &mut Account<'info, GlobalState>,
+    bet: &mut Account<'info, BetAccount>,
+    global_vault_usdc: &Account<'info, TokenAccount>,
+    player_usdc_ata: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<Settlement> {
+    let refund: u64 = bet.chips.iter().map(|chip| chip.stake / 2).sum();
+    if refund > 0 {
+        transfer_from_vault(gs, global_vault_usdc, player_usdc_ata, token_program, refund)?;
+    }
+
+    table.liquidity = table.liquidity.saturating_sub(refund);
+    let pnl = bet.stake.saturating_sub(refund) as i64;
+    table.realized_pnl = table.realized_pnl.saturating_add(pnl);
+    distribute_house_pnl(table, pnl)?;
+    let variant = table.variant;
+    table.exposure.remove(&bet.chips, variant, true)?;
+    release_liability(table, gs, 1);
+    let breaker_tripped = trip_breaker_if_below_floor(gs, global_vault_usdc.amount.saturating_sub(refund));
+
+    bet.state = BetState::Resolved;
+
+    Ok(Settlement {
+        amount: refund,
+        fees: Fees::default(),
+        tip: 0,
+        imprisoned_stake: 0,
+        breaker_tripped,
+    })
+}
+
+/// Pays a bet's escrowed crank tip to whoever settled or refunded it.
+fn pay_crank_tip<'info>(
+    gs: &Account<'info, GlobalState>,
+    global_vault_usdc: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    tip: u64,
+) -> Result<()> {
+    if tip == 0 {
+        return Ok(());
+    }
+    transfer_from_vault(gs, global_vault_usdc, to, token_program, tip)
+}
+
+/// Pauses betting on every table of the mint once free vault liquidity drops below the floor.
+/// Returns whether this call tripped the breaker.
+fn trip_breaker_if_below_floor(gs: &mut GlobalState, vault_balance: u64) -> bool {
+    if !gs.paused && below_liquidity_floor(gs, vault_balance) {
+        gs.paused = true;
+        return true;
+    }
+    false
+}
+
+fn below_liquidity_floor(gs: &GlobalState, vault_balance: u64) -> bool {
+    gs.liquidity_floor > 0 && vault_balance.saturating_sub(gs.total_locked_liability) < gs.liquidity_floor
+}
+
+/// Moves USDC out of the global vault, signed by the GlobalState PDA.
+fn transfer_from_vault<'info>(
+    gs: &Account<'info, GlobalState>,
+    global_vault_usdc: &Account<'info, TokenAccount>,
+    to: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[b"global", gs.usdc_mint.as_ref(), &[gs.bumps.global]]];
+    let cpi_accounts = Transfer {
+        from: global_vault_usdc.to_account_info(),
+        to: to.to_account_info(),
+        authority: gs.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer_seeds);
+    token::transfer(cpi_ctx, amount)
+}
+
+/// Pays the operator out of the table's ledger, burning the operator shares that back
+/// `amount`. In Public mode the amount must match a request older than the withdraw delay.
+/// Shared by `execute_withdraw` and governance withdrawals.
+#[allow(clippy::too_many_arguments)]
+fn withdraw_operator_liquidity<'info>(
+    table: &mut Account<'info, Table>,
+    gs: &Account<'info, GlobalState>,
+    global_vault_usdc: &Account<'info, TokenAccount>,
+    operator_usdc_ata: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+    now: i64,
+) -> Result<()> {
+    require!(amount > 0, RouletteError::InvalidAmount);
+    if table.mode == TableMode::Public {
+        require!(table.withdraw_request_amount == amount, RouletteError::WithdrawRequestMismatch);
+        require!(
+            now.saturating_sub(table.withdraw_request_ts) >= table.withdraw_delay,
+            RouletteError::WithdrawDelayNotPassed
+        );
+        table.withdraw_request_amount = 0;
+        table.withdraw_request_ts = 0;
+    }
+    require!(table.active_bets == 0, RouletteError::ActiveBetsExist);
+    require!(table.locked_liability == 0, RouletteError::LiabilityLocked);
+
+    // A table can only take out what its own ledger holds, never other tables' liquidity
+    require!(
+        table_available_liquidity(table) >= amount,
+        RouletteError::InsufficientTableLiquidity
+    );
+
+    // ...and the operator only the part of it backed by operator shares, not LP capital
+    let shares = shares_for_withdraw(table, amount)?;
+    require!(table.operator_shares >= shares, RouletteError::InsufficientShares);
+
+    // Withdraw from shared global vault (signed by GlobalState PDA)
+    transfer_from_vault(gs, global_vault_usdc, operator_usdc_ata, token_program, amount)?;
+
+    table.liquidity = table.liquidity.checked_sub(amount).ok_or(RouletteError::MathOverflow)?;
+    table.deposited_capital = table.deposited_capital.saturating_sub(amount);
+    table.operator_shares = table.operator_shares.checked_sub(shares).ok_or(RouletteError::MathOverflow)?;
+    table.total_shares = table.total_shares.checked_sub(shares).ok_or(RouletteError::MathOverflow)?;
+    Ok(())
+}
+
+/// Pays the outgoing operator the NAV of their shares on a handover, so the position
+/// stays with the key that funded it instead of passing to the next operator.
+fn settle_operator_position<'info>(
+    table: &mut Account<'info, Table>,
+    gs: &Account<'info, GlobalState>,
+    global_vault_usdc: &Account<'info, TokenAccount>,
+    operator_usdc_ata: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+) -> Result<u64> {
+    let shares = table.operator_shares;
+    if shares == 0 {
+        return Ok(0);
+    }
+    let amount = assets_for_shares(table, shares)?;
+    if amount > 0 {
+        transfer_from_vault(gs, global_vault_usdc, operator_usdc_ata, token_program, amount)?;
+    }
+
+    table.liquidity = table.liquidity.checked_sub(amount).ok_or(RouletteError::MathOverflow)?;
+    table.deposited_capital = table.deposited_capital.saturating_sub(amount);
+    table.total_shares = table.total_shares.checked_sub(shares).ok_or(RouletteError::MathOverflow)?;
+    table.operator_shares = 0;
+    Ok(amount)
+}
+
+/// Folds a settled bet's house P&L into the reward stream. Profit is shared only once
+/// earlier losses are made up; the shared part leaves the table's liquidity for the
+/// reward pool and raises the per-GOV index. P&L booked while sharing is off (no share
+/// configured or no GOV deposited) stays with LPs and is not carried forward.
+fn distribute_house_pnl(table: &mut Table, pnl: i64) -> Result<()> {
+    if table.profit_share_bps == 0 || table.total_gov_deposited == 0 {
+        table.undistributed_pnl = 0;
+        return Ok(());
+    }
+    table.undistributed_pnl = table.undistributed_pnl.saturating_add(pnl);
+    if table.undistributed_pnl <= 0 {
+        return Ok(());
+    }
+
+    let profit = table.undistributed_pnl as u64;
+    table.undistributed_pnl = 0;
+    let reward = profit
+        .checked_mul(table.profit_share_bps as u64)
+        .ok_or(RouletteError::MathOverflow)?
+        / BPS_DENOMINATOR;
+    if reward == 0 {
+        return Ok(());
+    }
+
+    table.liquidity = table.liquidity.saturating_sub(reward);
+    table.reward_pool = table.reward_pool.saturating_add(reward);
+    table.reward_index = table
+        .reward_index
+        .checked_add((reward as u128) * REWARD_INDEX_SCALE / table.total_gov_deposited as u128)
+        .ok_or(RouletteError::MathOverflow)?;
+    Ok(())
+}
+
+/// Books rewards accrued on `dep` since its last sync; call before changing its amount.
+fn sync_rewards(table: &mut Table, dep: &mut GovDeposit) -> Result<()> {
+    let earned = (dep.amount as u128)
+        .checked_mul(table.reward_index.saturating_sub(dep.reward_index))
+        .ok_or(RouletteError::MathOverflow)?
+        / REWARD_INDEX_SCALE;
+    let earned = u64::try_from(earned).map_err(|_| error!(RouletteError::MathOverflow))?;
+    dep.pending_rewards = dep.pending_rewards.checked_add(earned).ok_or(RouletteError::MathOverflow)?;
+    table.rewards_owed = table.rewards_owed.checked_add(earned).ok_or(RouletteError::MathOverflow)?;
+    dep.reward_index = table.reward_index;
+    Ok(())
+}
+
+/// Voting weight multiplier for a lock of `duration` seconds, in basis points.
+fn lock_boost_bps(duration: i64) -> u16 {
+    let duration = duration.clamp(0, MAX_GOV_LOCK_SECONDS) as u64;
+    let bonus = MAX_LOCK_BOOST_BPS * duration / MAX_GOV_LOCK_SECONDS as u64;
+    (BPS_DENOMINATOR + bonus) as u16
 }
 
-// ORAO program wrapper (Anchor requires type)
-#[derive(Clone)]
-pub struct OraoVrfProgram;
-impl anchor_lang::Id for OraoVrfProgram {
-    fn id() -> Pubkey {
-        Pubkey::from_str("VRFzZoJdhFWL8rkvu87LpKM3RbcVezpMEc6X5GVDr7y").unwrap()
+/// A deposit's voting weight: boosted while its lockup lasts, the plain amount after.
+fn voting_weight(dep: &GovDeposit, now: i64) -> Result<u64> {
+    if now >= dep.lock_until {
+        return Ok(dep.amount);
     }
+    let v = (dep.amount as u128)
+        .checked_mul(dep.lock_boost_bps as u128)
+        .ok_or(RouletteError::MathOverflow)?
+        / BPS_DENOMINATOR as u128;
+    u64::try_from(v).map_err(|_| error!(RouletteError::MathOverflow))
 }
 
-// -------------------- State --------------------
+/// GOV a key controls: `control` is the raw amount used for operator thresholds, `voting`
+/// the lockup-boosted weight used in proposals.
+struct GovPower {
+    control: u64,
+    voting: u64,
+}
 
-#[account]
-pub struct Table {
-    pub seed: u64,
-    pub creator: Pubkey,
-    pub operator: Pubkey,
+/// Loads the deposits delegated to `holder`, rejecting foreign, undelegated or repeated ones.
+fn delegated_deposits<'info>(
+    table: Pubkey,
+    holder: Pubkey,
+    accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<Account<'info, GovDeposit>>> {
+    let mut deposits: Vec<Account<'info, GovDeposit>> = Vec::with_capacity(accounts.len());
+    for info in accounts {
+        let dep = Account::<GovDeposit>::try_from(info)?;
+        require_keys_eq!(dep.table, table, RouletteError::DelegationMismatch);
+        require_keys_eq!(dep.delegate, holder, RouletteError::DelegationMismatch);
+        require!(
+            !deposits.iter().any(|d| d.key() == dep.key()),
+            RouletteError::DuplicateGovDeposit
+        );
+        deposits.push(dep);
+    }
+    Ok(deposits)
+}
 
-    pub mode: TableMode,
-    pub paused: bool,
+/// Whether `dep` currently counts towards the table operator's control power.
+fn backs_operator(table: &Table, dep: &GovDeposit) -> bool {
+    dep.delegate == table.operator || (dep.delegate == Pubkey::default() && dep.depositor == table.operator)
+}
 
-    pub usdc_mint: Pubkey,
-    pub gov_mint: Pubkey,
-    pub global_state: Pubkey,
-    pub control_vault_gov: Pubkey,
+/// Operator control power left once `removed` GOV of `dep` stops backing them. `others` are
+/// the remaining deposits backing the operator (their own and ones delegated to them).
+fn operator_power_without<'info>(
+    table: &Table,
+    dep: &Account<'info, GovDeposit>,
+    removed: u64,
+    others: &'info [AccountInfo<'info>],
+) -> Result<u64> {
+    let mut seen = vec![dep.key()];
+    let mut power = dep.amount.saturating_sub(removed);
+    for info in others {
+        let other = Account::<GovDeposit>::try_from(info)?;
+        require_keys_eq!(other.table, dep.table, RouletteError::DelegationMismatch);
+        require!(backs_operator(table, &other), RouletteError::DelegationMismatch);
+        require!(!seen.contains(&other.key()), RouletteError::DuplicateGovDeposit);
+        seen.push(other.key());
+        power = power.checked_add(other.amount).ok_or(RouletteError::MathOverflow)?;
+    }
+    Ok(power)
+}
 
-    pub min_bet: u64,
-    pub max_bet: u64,
+/// Power of `holder`: their own deposit unless delegated away, plus `delegated`.
+fn holder_power(own: &GovDeposit, holder: &Pubkey, delegated: &[Account<GovDeposit>], now: i64) -> Result<GovPower> {
+    let mut power = GovPower { control: 0, voting: 0 };
+    let own_counts = own.depositor == *holder && own.delegate == Pubkey::default();
+    for dep in own_counts.then_some(own).into_iter().chain(delegated.iter().map(|d| &**d)) {
+        power.control = power.control.checked_add(dep.amount).ok_or(RouletteError::MathOverflow)?;
+        power.voting = power
+            .voting
+            .checked_add(voting_weight(dep, now)?)
+            .ok_or(RouletteError::MathOverflow)?;
+    }
+    Ok(power)
+}
 
-    pub locked_liability: u64,
-    pub active_bets: u32,
-    pub bet_seq: u64,
+/// Operator handovers must not strand pending bets or a queued withdrawal mid-flight.
+/// A withdraw request left unexecuted for a challenge period past its delay no longer
+/// blocks, so an incumbent cannot hold the table with a stale request.
+fn require_operator_change_allowed(table: &Table, now: i64) -> Result<()> {
+    require!(table.active_bets == 0, RouletteError::ActiveBetsExist);
+    let request_expires = table
+        .withdraw_request_ts
+        .saturating_add(table.withdraw_delay)
+        .saturating_add(OPERATOR_CLAIM_CHALLENGE_SECONDS);
+    require!(
+        table.withdraw_request_ts == 0 || now >= request_expires,
+        RouletteError::WithdrawRequestPending
+    );
+    Ok(())
+}
 
-    pub withdraw_request_ts: i64,
-    pub withdraw_request_amount: u64,
+/// `bps` basis points of `amount`, rounded up.
+fn bps_of(amount: u64, bps: u64) -> Result<u64> {
+    let v = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(RouletteError::MathOverflow)?
+        .div_ceil(BPS_DENOMINATOR as u128);
+    u64::try_from(v).map_err(|_| error!(RouletteError::MathOverflow))
+}
 
-    pub bumps: TableBumps,
+/// GOV needed to operate the table given the GOV mint's current supply.
+fn operator_threshold(table: &Table, gov_supply: u64) -> Result<u64> {
+    bps_of(gov_supply, table.operator_threshold_bps as u64)
 }
 
-impl Table {
-    pub const SIZE: usize = 8 + 32 + 32
-        + 1 + 1
-        + 32 + 32 + 32 + 32
-        + 8 + 8
-        + 8 + 4 + 8
-        + 8 + 8
-        + TableBumps::SIZE;
+/// A proposal passes with a simple majority of voting weight once enough deposited GOV has
+/// voted. Quorum counts unboosted GOV, in the same unit as the supply it is taken from.
+fn proposal_passed(proposal: &Proposal) -> bool {
+    proposal.yes_votes > proposal.no_votes && proposal.turnout >= proposal.quorum
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
-pub enum TableMode {
-    Private = 0,
-    Public = 1,
+/// Decodes an ORAO randomness account: `Some` once fulfilled, `None` while still pending.
+fn read_randomness(random: &AccountInfo) -> Result<Option<[u8; 64]>> {
+    let mut data: &[u8] = &random.data.borrow();
+    let randomness_state = RandomnessAccountData::try_deserialize_unchecked(&mut data)
+        .map_err(|_| RouletteError::RandomnessDecodeFailed)?;
+    Ok(randomness_state.fulfilled_randomness())
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub struct TableBumps {
-    pub table: u8,
-    pub control_vault_gov: u8,
+/// What an expired-bet refund is allowed to do.
+#[derive(Debug, PartialEq, Eq)]
+enum RefundDecision {
+    /// Randomness is already known: settle through the normal outcome path.
+    Settle(u8),
+    /// Randomness is genuinely unfulfilled and the timeout has passed.
+    Refund,
 }
-impl TableBumps {
-    pub const SIZE: usize = 1 + 1;
+
+/// Switches the table's mode. Leaving Public mode lifts the timelocks players and LPs rely
+/// on, so it is queued instead; returns when it may be applied. Shared by `set_mode` and
+/// governance.
+fn change_mode(table: &mut Table, mode: TableMode, now: i64) -> Option<i64> {
+    if table.mode == TableMode::Public && mode != TableMode::Public {
+        table.pending_mode = mode;
+        table.mode_request_ts = now;
+        return Some(now.saturating_add(table.withdraw_delay));
+    }
+    table.mode = mode;
+    table.mode_request_ts = 0;
+    None
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
-pub struct GlobalBumps {
-    pub global: u8,
-    pub vault_usdc: u8,
+fn set_paused(table: &mut Table, paused: bool) {
+    table.paused = paused;
 }
-impl GlobalBumps {
-    pub const SIZE: usize = 1 + 1;
+
+/// Applies a config change, or queues it when it is worse for players in Public mode;
+/// returns when a queued change may be applied. Shared by `update_table_config` and
+/// governance.
+fn change_config(table: &mut Table, config: TableConfig, now: i64) -> Result<Option<i64>> {
+    validate_table_config(&config)?;
+    if table.mode == TableMode::Public && config_harms_players(&table.config(), &config) {
+        table.pending_config = config;
+        table.config_request_ts = now;
+        return Ok(Some(now.saturating_add(table.withdraw_delay)));
+    }
+    table.apply_config(&config);
+    table.config_request_ts = 0;
+    Ok(None)
 }
 
-#[account]
-pub struct GlobalState {
-    pub usdc_mint: Pubkey,
-    pub vault_usdc: Pubkey,
-    pub total_locked_liability: u64,
-    pub total_active_bets: u64,
-    pub bumps: GlobalBumps,
+fn validate_table_config(config: &TableConfig) -> Result<()> {
+    require!(config.min_bet > 0, RouletteError::InvalidBetRange);
+    require!(config.max_bet >= config.min_bet, RouletteError::InvalidBetRange);
+    require!(
+        (MIN_BET_TIMEOUT_SECONDS..=MAX_BET_TIMEOUT_SECONDS).contains(&config.bet_timeout),
+        RouletteError::InvalidTableConfig
+    );
+    require!(
+        (MIN_WITHDRAW_DELAY_SECONDS..=MAX_WITHDRAW_DELAY_SECONDS).contains(&config.withdraw_delay),
+        RouletteError::InvalidTableConfig
+    );
+    require!(
+        config.enabled_kinds != 0 && config.enabled_kinds & !BetKind::ALL == 0,
+        RouletteError::InvalidTableConfig
+    );
+    require!(
+        config.profit_share_bps as u64 <= BPS_DENOMINATOR,
+        RouletteError::InvalidTableConfig
+    );
+    require!(config.operator_fee_bps <= MAX_FEE_BPS, RouletteError::FeeTooHigh);
+    require!(
+        config.max_payout_bps as u64 <= BPS_DENOMINATOR,
+        RouletteError::InvalidTableConfig
+    );
+    Ok(())
 }
-impl GlobalState {
-    pub const SIZE: usize = 32 + 32 + 8 + 8 + GlobalBumps::SIZE;
+
+/// Whether moving from `current` to `next` is worse for players or LPs: tighter limits or caps,
+/// fewer bet kinds, a higher fee or tip, a longer wait for refunds or a shorter withdraw notice. Any change to
+/// the profit share moves value between LPs and GOV depositors, so it always counts.
+fn config_harms_players(current: &TableConfig, next: &TableConfig) -> bool {
+    next.profit_share_bps != current.profit_share_bps
+        || next.operator_fee_bps > current.operator_fee_bps
+        || next.crank_tip > current.crank_tip
+        || (next.max_number_exposure != 0
+            && (current.max_number_exposure == 0 || next.max_number_exposure < current.max_number_exposure))
+        || (next.max_payout_bps != 0
+            && (current.max_payout_bps == 0 || next.max_payout_bps < current.max_payout_bps))
+        || next.min_bet > current.min_bet
+        || next.max_bet < current.max_bet
+        || next.bet_timeout > current.bet_timeout
+        || next.withdraw_delay < current.withdraw_delay
+        || current.enabled_kinds & !next.enabled_kinds != 0
 }
 
-#[account]
-pub struct GovDeposit {
-    pub table: Pubkey,
-    pub depositor: Pubkey,
-    pub amount: u64,
+/// Books a round bet that just settled or refunded out of the round's open bets; one sent
+/// to prison stays on the round until it is spun or released.
+fn round_bet_settled(round: &mut Round, bet: &BetAccount) {
+    round.open_bets = round.open_bets.saturating_sub(1);
+    if bet.state == BetState::Imprisoned {
+        round.imprisoned_bets = round.imprisoned_bets.saturating_add(1);
+    }
 }
-impl GovDeposit {
-    pub const SIZE: usize = 32 + 32 + 8;
+
+/// Detaches a prisoner from the round that imprisoned it, so its own next spin settles it
+/// like a single-spin bet. Bets placed outside a round pass no round.
+fn prisoner_leaves_round(round: Option<&mut Round>, bet: &mut BetAccount) -> Result<()> {
+    if bet.round == Pubkey::default() {
+        return Ok(());
+    }
+    let round = round.ok_or(RouletteError::RoundBetMismatch)?;
+    round.imprisoned_bets = round.imprisoned_bets.saturating_sub(1);
+    bet.round = Pubkey::default();
+    Ok(())
 }
 
-#[account]
-pub struct BetAccount {
-    pub table: Pubkey,
-    pub player: Pubkey,
+/// Bets waiting on a spin: pending ones, and imprisoned ones whose next spin was requested.
+fn require_awaiting_spin(bet: &BetAccount) -> Result<()> {
+    match bet.state {
+        BetState::Pending => Ok(()),
+        BetState::Imprisoned => {
+            require!(
+                bet.randomness_account != Pubkey::default(),
+                RouletteError::ImprisonedBetNotSpun
+            );
+            Ok(())
+        }
+        _ => err!(RouletteError::BetNotPending),
+    }
+}
 
-    pub stake: u64,
-    pub multiplier: u16,
-    pub max_total_payout: u64,
+fn refund_decision(
+    randomness: Option<[u8; 64]>,
+    variant: TableVariant,
+    since_ts: i64,
+    timeout: i64,
+    now: i64,
+) -> Result<RefundDecision> {
+    if let Some(rnd) = randomness {
+        return Ok(RefundDecision::Settle(roulette_number_from_randomness(&rnd, variant)));
+    }
+    require!(
+        now.saturating_sub(since_ts) >= timeout,
+        RouletteError::BetNotExpired
+    );
+    Ok(RefundDecision::Refund)
+}
 
-    pub kind: BetKind,
-    pub state: BetState,
+/// Books liquidity that has no shares outstanding against it (rounding dust, or house P&L
+/// left after every share was redeemed) to the operator's position, so the next depositor
+/// cannot mint it for free.
+fn adopt_orphaned_liquidity(table: &mut Table) {
+    if table.total_shares == 0 && table.liquidity > 0 {
+        table.operator_shares = table.liquidity;
+        table.total_shares = table.liquidity;
+    }
+}
 
-    pub created_ts: i64,
-    pub force: [u8; 32],
+/// Shares minted for a deposit of `amount`. Deposits are priced as if every pending bet
+/// loses and redemptions as if every one pays out in full, so neither side can profit from
+/// bets that are still pending, even once their outcome is known. Shares left with no NAV
+/// behind them are worth nothing, so they barely dilute a new deposit.
+fn shares_for_deposit(table: &Table, amount: u64) -> Result<u64> {
+    if table.total_shares == 0 {
+        return Ok(amount);
+    }
+    let nav = table.liquidity.max(1);
+    let shares = (amount as u128)
+        .checked_mul(table.total_shares as u128)
+        .ok_or(RouletteError::MathOverflow)?
+        / nav as u128;
+    require!(shares > 0, RouletteError::InvalidAmount);
+    u64::try_from(shares).map_err(|_| RouletteError::MathOverflow.into())
+}
 
-    pub randomness_account: Pubkey,
-    pub result_number: Option<u8>,
+/// USDC value of `shares` at the table's NAV net of locked liability (rounded down).
+fn assets_for_shares(table: &Table, shares: u64) -> Result<u64> {
+    require!(shares <= table.total_shares, RouletteError::InsufficientShares);
+    let nav = table_available_liquidity(table);
+    let amount = (shares as u128)
+        .checked_mul(nav as u128)
+        .ok_or(RouletteError::MathOverflow)?
+        / table.total_shares as u128;
+    Ok(amount as u64)
 }
-impl BetAccount {
-    pub const SIZE: usize = 32 + 32
-        + 8 + 2 + 8
-        + BetKind::MAX_SIZE + 1
-        + 8 + 32
-        + 32 + 2;
+
+/// Shares that must be burned to take `amount` out at the current NAV (rounded up).
+fn shares_for_withdraw(table: &Table, amount: u64) -> Result<u64> {
+    let nav = table_available_liquidity(table);
+    require!(nav > 0, RouletteError::ZeroNav);
+    let shares = (amount as u128)
+        .checked_mul(table.total_shares as u128)
+        .ok_or(RouletteError::MathOverflow)?
+        .div_ceil(nav as u128);
+    u64::try_from(shares).map_err(|_| RouletteError::MathOverflow.into())
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
-pub enum BetState {
-    Pending = 0,
-    Resolved = 1,
-    Refunded = 2,
+/// Returned to the player (stake included) by one chip on outcome `n`. An imprisoned chip
+/// only gets its stake back, and only if it wins outright.
+fn chip_payout(chip: &BetChip, n: u8, variant: TableVariant, imprisoned: bool) -> Result<u64> {
+    if !bet_covers_number(&chip.kind, n) {
+        if variant == TableVariant::LaPartage && !imprisoned && is_zero_pocket(n) && is_even_money(&chip.kind) {
+            return Ok(chip.stake / 2);
+        }
+        return Ok(0);
+    }
+    if imprisoned {
+        return Ok(chip.stake);
+    }
+    Ok(chip
+        .stake
+        .checked_mul(bet_multiplier(&chip.kind) as u64 + 1)
+        .ok_or(RouletteError::MathOverflow)?)
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub enum BetKind {
-    Straight { number: u8 },
-    Split { a: u8, b: u8 },
-    Street { row: u8 },
-    Corner { row: u8, col: u8 },
-    SixLine { row: u8 },
-    Red,
-    Black,
-    Even,
-    Odd,
-    Low,
-    High,
-    Dozen { idx: u8 },
-    Column { idx: u8 },
+/// Whether a chip is imprisoned instead of lost on outcome `n` (En Prison tables only).
+fn goes_to_prison(chip: &BetChip, n: u8, variant: TableVariant, imprisoned: bool) -> bool {
+    variant == TableVariant::EnPrison && !imprisoned && is_zero_pocket(n) && is_even_money(&chip.kind)
 }
-impl BetKind {
-    pub const MAX_SIZE: usize = 1 + 2;
+
+/// Total returned to the player (stakes included) by the chips on outcome `n`.
+fn chips_payout(chips: &[BetChip], n: u8, variant: TableVariant, imprisoned: bool) -> Result<u64> {
+    let mut total: u64 = 0;
+    for chip in chips.iter() {
+        let payout = chip_payout(chip, n, variant, imprisoned)?;
+        total = total.checked_add(payout).ok_or(RouletteError::MathOverflow)?;
+    }
+    Ok(total)
 }
 
-// -------------------- Helpers --------------------
+/// Most the chips can still cost the house on outcome `n`: their payout, plus the stake of
+/// chips sent to prison, which they may win back on the next spin.
+fn chips_exposure(chips: &[BetChip], n: u8, variant: TableVariant, imprisoned: bool) -> Result<u64> {
+    let mut total = chips_payout(chips, n, variant, imprisoned)?;
+    for chip in chips.iter().filter(|c| goes_to_prison(c, n, variant, imprisoned)) {
+        total = total.checked_add(chip.stake).ok_or(RouletteError::MathOverflow)?;
+    }
+    Ok(total)
+}
 
-#[macro_export]
-macro_rules! table_signer_seeds {
-    ($table:expr) => {{
-        &[ &[ b"table", $table.creator.as_ref(), &$table.seed.to_le_bytes(), &[$table.bumps.table] ] ]
-    }};
+/// Largest payout of a chip layout across all outcomes of the wheel.
+fn chips_max_payout(chips: &[BetChip], variant: TableVariant) -> Result<u64> {
+    let mut max = 0;
+    for n in 0..variant.pockets() {
+        max = max.max(chips_exposure(chips, n, variant, false)?);
+    }
+    Ok(max)
 }
 
-fn roulette_number_from_randomness(rnd: &[u8; 64]) -> u8 {
+fn roulette_number_from_randomness(rnd: &[u8; 64], variant: TableVariant) -> u8 {
     let mut bytes = [0u8; 16];
     bytes.copy_from_slice(&rnd[0..16]);
     let x = u128::from_le_bytes(bytes);
-    (x % (ROULETTE_MOD as u128)) as u8
+    (x % (variant.pockets() as u128)) as u8
+}
+
+fn is_zero_pocket(n: u8) -> bool {
+    n == 0 || n == DOUBLE_ZERO
+}
+
+fn is_even_money(b: &BetKind) -> bool {
+    matches!(
+        b,
+        BetKind::Red | BetKind::Black | BetKind::Even | BetKind::Odd | BetKind::Low | BetKind::High
+    )
 }
 
 fn bet_multiplier(b: &BetKind) -> u16 {
@@ -945,18 +5075,36 @@ fn bet_multiplier(b: &BetKind) -> u16 {
         BetKind::Low | BetKind::High => 1,
         BetKind::Dozen { .. } => 2,
         BetKind::Column { .. } => 2,
+        BetKind::Trio { .. } => 11,
+        BetKind::Basket => 8,
+        BetKind::TopLine => 6,
     }
 }
 
-fn validate_bet_kind(b: &BetKind) -> Result<()> {
+fn validate_bet_kind(b: &BetKind, variant: TableVariant) -> Result<()> {
     match b {
         BetKind::Straight { number } => {
-            require!(*number <= 36, RouletteError::InvalidNumber);
+            require!(*number < variant.pockets(), RouletteError::InvalidNumber);
         }
         BetKind::Split { a, b } => {
-            require!(*a <= 36 && *b <= 36 && *a != *b, RouletteError::InvalidSplit);
-            require!(*a != 0 && *b != 0, RouletteError::InvalidSplit);
-            require!(are_adjacent(*a, *b), RouletteError::InvalidSplit);
+            let (lo, hi) = (*a.min(b), *a.max(b));
+            require!(lo != hi && hi < variant.pockets(), RouletteError::InvalidSplit);
+            if is_zero_pocket(lo) || is_zero_pocket(hi) {
+                require!(zero_splits(variant).contains(&[lo, hi]), RouletteError::InvalidSplit);
+            } else {
+                require!(are_adjacent(lo, hi), RouletteError::InvalidSplit);
+            }
+        }
+        BetKind::Trio { a, b, c } => {
+            let mut numbers = [*a, *b, *c];
+            numbers.sort_unstable();
+            require!(trios(variant).contains(&numbers), RouletteError::InvalidTrio);
+        }
+        BetKind::Basket => {
+            require!(variant != TableVariant::American, RouletteError::BetNotOnThisWheel);
+        }
+        BetKind::TopLine => {
+            require!(variant == TableVariant::American, RouletteError::BetNotOnThisWheel);
         }
         BetKind::Street { row } => {
             require!(*row >= 1 && *row <= 12, RouletteError::InvalidStreet);
@@ -979,6 +5127,80 @@ fn validate_bet_kind(b: &BetKind) -> Result<()> {
     Ok(())
 }
 
+/// Pockets of the single-zero wheel in clockwise order, starting at 0.
+const EUROPEAN_WHEEL: [u8; 37] = [
+    0, 32, 15, 19, 4, 21, 2, 25, 17, 34, 6, 27, 13, 36, 11, 30, 8, 23, 10, 5, 24, 16, 33, 1, 20, 14, 31, 9, 22,
+    18, 29, 7, 28, 12, 35, 3, 26,
+];
+
+/// Chips of an announced bet at `unit` stake per betting unit.
+fn expand_announced_bet(call: &AnnouncedBet, unit: u64, variant: TableVariant) -> Result<Vec<BetChip>> {
+    require!(variant != TableVariant::American, RouletteError::BetNotOnThisWheel);
+    let double = unit.checked_mul(2).ok_or(RouletteError::MathOverflow)?;
+    let split = |a, b| BetChip { kind: BetKind::Split { a, b }, stake: unit };
+    let chips = match *call {
+        AnnouncedBet::VoisinsDuZero => vec![
+            BetChip { kind: BetKind::Trio { a: 0, b: 2, c: 3 }, stake: double },
+            split(4, 7),
+            split(12, 15),
+            split(18, 21),
+            split(19, 22),
+            BetChip { kind: BetKind::Corner { row: 9, col: 1 }, stake: double },
+            split(32, 35),
+        ],
+        AnnouncedBet::Tiers => vec![
+            split(5, 8),
+            split(10, 11),
+            split(13, 16),
+            split(23, 24),
+            split(27, 30),
+            split(33, 36),
+        ],
+        AnnouncedBet::Orphelins => vec![
+            BetChip { kind: BetKind::Straight { number: 1 }, stake: unit },
+            split(6, 9),
+            split(14, 17),
+            split(17, 20),
+            split(31, 34),
+        ],
+        AnnouncedBet::Neighbours { number, neighbours } => {
+            let center = EUROPEAN_WHEEL
+                .iter()
+                .position(|n| *n == number)
+                .ok_or(RouletteError::InvalidAnnouncedBet)?;
+            require!(
+                neighbours >= 1 && 2 * (neighbours as usize) < BetAccount::MAX_CHIPS,
+                RouletteError::InvalidAnnouncedBet
+            );
+            let len = EUROPEAN_WHEEL.len();
+            let start = center + len - neighbours as usize;
+            (0..2 * neighbours as usize + 1)
+                .map(|i| BetChip {
+                    kind: BetKind::Straight { number: EUROPEAN_WHEEL[(start + i) % len] },
+                    stake: unit,
+                })
+                .collect()
+        }
+    };
+    Ok(chips)
+}
+
+/// Splits touching a zero on the layout, as `[low, high]` (00 is 37).
+fn zero_splits(variant: TableVariant) -> &'static [[u8; 2]] {
+    match variant {
+        TableVariant::American => &[[0, 1], [0, 2], [0, DOUBLE_ZERO], [2, DOUBLE_ZERO], [3, DOUBLE_ZERO]],
+        _ => &[[0, 1], [0, 2], [0, 3]],
+    }
+}
+
+/// Trios, in ascending order (00 is 37).
+fn trios(variant: TableVariant) -> &'static [[u8; 3]] {
+    match variant {
+        TableVariant::American => &[[0, 1, 2], [0, 2, DOUBLE_ZERO], [2, 3, DOUBLE_ZERO]],
+        _ => &[[0, 1, 2], [0, 2, 3]],
+    }
+}
+
 fn are_adjacent(a: u8, b: u8) -> bool {
     let (ra, ca) = num_to_row_col(a);
     let (rb, cb) = num_to_row_col(b);
@@ -1009,13 +5231,16 @@ fn bet_covers_number(b: &BetKind, n: u8) -> bool {
     match b {
         BetKind::Straight { number } => *number == n,
         BetKind::Split { a, b } => *a == n || *b == n,
+        BetKind::Trio { a, b, c } => *a == n || *b == n || *c == n,
+        BetKind::Basket => n <= 3,
+        BetKind::TopLine => n <= 3 || n == DOUBLE_ZERO,
         BetKind::Street { row } => {
-            if n == 0 { return false; }
-            let start = 3 * (*row as u8) - 2;
-            n >= start && n <= start + 2
+            if is_zero_pocket(n) { return false; }
+            let start = 3 * *row - 2;
+            (start..=start + 2).contains(&n)
         }
         BetKind::Corner { row, col } => {
-            if n == 0 { return false; }
+            if is_zero_pocket(n) { return false; }
             let tl = ((*row as u16 - 1) * 3 + (*col as u16)) as u8;
             let tr = tl + 1;
             let bl = tl + 3;
@@ -1023,24 +5248,24 @@ fn bet_covers_number(b: &BetKind, n: u8) -> bool {
             n == tl || n == tr || n == bl || n == br
         }
         BetKind::SixLine { row } => {
-            if n == 0 { return false; }
-            let start = 3 * (*row as u8) - 2;
-            n >= start && n <= start + 5
+            if is_zero_pocket(n) { return false; }
+            let start = 3 * *row - 2;
+            (start..=start + 5).contains(&n)
         }
-        BetKind::Red => n != 0 && is_red(n),
-        BetKind::Black => n != 0 && !is_red(n) && n <= 36,
-        BetKind::Even => n != 0 && (n % 2 == 0),
-        BetKind::Odd => n != 0 && (n % 2 == 1),
-        BetKind::Low => n >= 1 && n <= 18,
-        BetKind::High => n >= 19 && n <= 36,
+        BetKind::Red => is_red(n),
+        BetKind::Black => !is_zero_pocket(n) && !is_red(n),
+        BetKind::Even => !is_zero_pocket(n) && (n % 2 == 0),
+        BetKind::Odd => !is_zero_pocket(n) && (n % 2 == 1),
+        BetKind::Low => (1..=18).contains(&n),
+        BetKind::High => (19..=36).contains(&n),
         BetKind::Dozen { idx } => match idx {
-            1 => n >= 1 && n <= 12,
-            2 => n >= 13 && n <= 24,
-            3 => n >= 25 && n <= 36,
+            1 => (1..=12).contains(&n),
+            2 => (13..=24).contains(&n),
+            3 => (25..=36).contains(&n),
             _ => false,
         },
         BetKind::Column { idx } => {
-            if n == 0 { return false; }
+            if is_zero_pocket(n) { return false; }
             let (_r, c) = num_to_row_col(n);
             *idx == c
         }
@@ -1057,17 +5282,35 @@ pub enum RouletteError {
     InvalidAmount,
     #[msg("Paused")]
     Paused,
+    #[msg("Betting is paused globally")]
+    GloballyPaused,
+    #[msg("Bet would take the vault below its liquidity floor")]
+    BelowLiquidityFloor,
+    #[msg("Vault liquidity is above the floor")]
+    AboveLiquidityFloor,
     #[msg("Invalid stake")]
     InvalidStake,
     #[msg("Math overflow")]
     MathOverflow,
     #[msg("Insufficient liquidity for this bet")]
     InsufficientLiquidity,
+    #[msg("Bet would exceed the table's exposure cap on a number")]
+    NumberExposureCapExceeded,
+    #[msg("Bet payout exceeds the table's risk limit")]
+    BetExceedsRiskLimit,
+    #[msg("Amount exceeds this table's available liquidity")]
+    InsufficientTableLiquidity,
 
     #[msg("Invalid number")]
     InvalidNumber,
     #[msg("Invalid split")]
     InvalidSplit,
+    #[msg("Invalid trio")]
+    InvalidTrio,
+    #[msg("Invalid announced bet")]
+    InvalidAnnouncedBet,
+    #[msg("Bet is not offered on this table's wheel")]
+    BetNotOnThisWheel,
     #[msg("Invalid street")]
     InvalidStreet,
     #[msg("Invalid corner")]
@@ -1078,8 +5321,10 @@ pub enum RouletteError {
     InvalidDozen,
     #[msg("Invalid column")]
     InvalidColumn,
+    #[msg("Invalid number of chips")]
+    InvalidChipCount,
 
-    #[msg("Not enough GOV to operate (need >=51 deposited)")]
+    #[msg("Not enough GOV to operate this table")]
     NotEnoughGovToOperate,
     #[msg("Insufficient GOV deposit")]
     InsufficientGovDeposit,
@@ -1090,14 +5335,117 @@ pub enum RouletteError {
     BetNotPending,
     #[msg("Bet not expired yet")]
     BetNotExpired,
+    #[msg("Bet is still pending")]
+    BetStillPending,
+    #[msg("Bet is not imprisoned")]
+    BetNotImprisoned,
+    #[msg("Imprisoned bet has no spin requested yet")]
+    ImprisonedBetNotSpun,
+    #[msg("Imprisoned bet has already been spun")]
+    ImprisonedBetAlreadySpun,
+    #[msg("Operator threshold must be a majority of a non-empty GOV supply")]
+    InvalidOperatorThreshold,
+    #[msg("GOV mint does not match the table")]
+    GovMintMismatch,
+    #[msg("Operator fees must be claimed first")]
+    FeesUnclaimed,
+    #[msg("GOV depositors still have rewards to claim")]
+    RewardsUnclaimed,
+    #[msg("Fee exceeds the maximum")]
+    FeeTooHigh,
+    #[msg("Nothing to claim")]
+    NothingToClaim,
+    #[msg("GOV deposit is locked")]
+    GovLocked,
+    #[msg("Invalid lock duration")]
+    InvalidLockDuration,
+    #[msg("GOV deposit is not delegated to this key on this table")]
+    DelegationMismatch,
+    #[msg("GOV deposit passed more than once")]
+    DuplicateGovDeposit,
+    #[msg("Claimant is already the operator")]
+    AlreadyOperator,
+    #[msg("Another operator claim is pending")]
+    OperatorClaimPending,
+    #[msg("No matching operator claim is pending")]
+    NoOperatorClaim,
+    #[msg("Operator claim challenge window has closed")]
+    ChallengeWindowClosed,
+    #[msg("Operator claim challenge window is still open")]
+    ChallengeWindowOpen,
+    #[msg("Challenge needs the operator threshold in GOV")]
+    ChallengeTooSmall,
+    #[msg("A withdraw request is pending")]
+    WithdrawRequestPending,
+    #[msg("Token account is not owned by the table operator")]
+    OperatorTokenAccountMismatch,
+    #[msg("Proposal does not belong to this table")]
+    ProposalTableMismatch,
+    #[msg("Voting has ended")]
+    VotingClosed,
+    #[msg("Voting is still open")]
+    VotingStillOpen,
+    #[msg("Proposal did not pass")]
+    ProposalNotPassed,
+    #[msg("Proposal was already executed")]
+    ProposalAlreadyExecuted,
+    #[msg("Proposal execution window has passed")]
+    ProposalExpired,
+    #[msg("Accounts required by the proposal action are missing")]
+    MissingProposalAccounts,
+    #[msg("GOV is locked until the vote ends")]
+    GovLockedByVote,
+    #[msg("Table is retiring")]
+    TableRetiring,
+    #[msg("Table must be retired first")]
+    TableNotRetiring,
+    #[msg("GOV is still deposited in the table")]
+    GovStillDeposited,
+    #[msg("GOV deposit still holds GOV")]
+    GovDepositNotEmpty,
+    #[msg("Liquidity shares are still outstanding")]
+    SharesOutstanding,
+    #[msg("Bet kind is disabled on this table")]
+    BetKindDisabled,
+    #[msg("Invalid table config")]
+    InvalidTableConfig,
+    #[msg("No table config change is queued")]
+    NoPendingConfig,
+    #[msg("Table config change is still timelocked")]
+    ConfigTimelockActive,
+    #[msg("No mode change is queued")]
+    NoPendingModeChange,
+    #[msg("Mode change is still timelocked")]
+    ModeTimelockActive,
 
     #[msg("Randomness account decode failed")]
     RandomnessDecodeFailed,
     #[msg("Randomness not fulfilled yet")]
     RandomnessNotFulfilled,
+    #[msg("Randomness account does not match the bet")]
+    RandomnessAccountMismatch,
+    #[msg("Batch accounts must be (bet, randomness, player token account) triples")]
+    InvalidBatchAccounts,
+    #[msg("Round bets must be resolved through their round")]
+    RoundBetInBatch,
+
+    #[msg("Bet does not belong to this table")]
+    BetTableMismatch,
+    #[msg("Round does not belong to this table")]
+    RoundTableMismatch,
+    #[msg("Global state does not match the table")]
+    GlobalStateMismatch,
+    #[msg("Vault does not match the global state")]
+    VaultMismatch,
+    #[msg("Token account is not owned by the bet's player")]
+    PlayerTokenAccountMismatch,
+    #[msg("Token account mint does not match the table")]
+    TokenMintMismatch,
 
     #[msg("Global state PDA is not owned by this program")]
     InvalidGlobalStateOwner,
+    #[msg("Signer lacks the required authority")]
+    Unauthorized,
 
     #[msg("Active bets exist")]
     ActiveBetsExist,
@@ -1110,4 +5458,717 @@ pub enum RouletteError {
     WithdrawRequestMismatch,
     #[msg("Withdraw delay has not passed yet")]
     WithdrawDelayNotPassed,
+
+    #[msg("LP vault already initialized")]
+    LpVaultAlreadyInitialized,
+    #[msg("Insufficient shares")]
+    InsufficientShares,
+    #[msg("Table NAV is zero")]
+    ZeroNav,
+
+    #[msg("Invalid betting window")]
+    InvalidBettingWindow,
+    #[msg("Round is not open for bets")]
+    RoundNotOpen,
+    #[msg("Round betting window has not elapsed yet")]
+    RoundStillOpen,
+    #[msg("Round is not closed")]
+    RoundNotClosed,
+    #[msg("Round is not settled yet")]
+    RoundNotSettled,
+    #[msg("Round is already settled")]
+    RoundAlreadySettled,
+    #[msg("Bet does not match the round")]
+    RoundBetMismatch,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use orao_solana_vrf::state::{FulfilledRequest, PendingRequest, RandomnessV2, RequestAccount};
+
+    fn randomness_bytes(request: RequestAccount) -> Vec<u8> {
+        let mut buf = Vec::new();
+        RandomnessV2 { request }.try_serialize(&mut buf).unwrap();
+        buf
+    }
+
+    fn read(data: &mut [u8]) -> Result<Option<[u8; 64]>> {
+        let key = Pubkey::new_unique();
+        let owner = orao_solana_vrf::ID;
+        let mut lamports = 1;
+        let info = AccountInfo::new(&key, false, false, &mut lamports, data, &owner, false, 0);
+        read_randomness(&info)
+    }
+
+    #[test]
+    fn pending_randomness_reads_as_unfulfilled() {
+        let mut data = randomness_bytes(RequestAccount::Pending(PendingRequest {
+            client: Pubkey::new_unique(),
+            seed: [7u8; 32],
+            responses: vec![],
+        }));
+        assert_eq!(read(&mut data).unwrap(), None);
+    }
+
+    #[test]
+    fn fulfilled_randomness_reads_back() {
+        let mut data = randomness_bytes(RequestAccount::Fulfilled(FulfilledRequest {
+            client: Pubkey::new_unique(),
+            seed: [7u8; 32],
+            randomness: [3u8; 64],
+        }));
+        assert_eq!(read(&mut data).unwrap(), Some([3u8; 64]));
+    }
+
+    #[test]
+    fn garbage_randomness_fails_to_decode() {
+        let mut data = vec![0u8; 16];
+        assert!(read(&mut data).is_err());
+    }
+
+    #[test]
+    fn refund_of_unfulfilled_bet_waits_for_timeout() {
+        assert!(refund_decision(None, TableVariant::European, 1_000, BET_TIMEOUT_SECONDS, 1_000 + BET_TIMEOUT_SECONDS - 1).is_err());
+        assert_eq!(
+            refund_decision(None, TableVariant::European, 1_000, BET_TIMEOUT_SECONDS, 1_000 + BET_TIMEOUT_SECONDS).unwrap(),
+            RefundDecision::Refund
+        );
+    }
+
+    #[test]
+    fn config_changes_against_players_are_flagged() {
+        let current = TableConfig {
+            min_bet: 10,
+            max_bet: 100,
+            bet_timeout: BET_TIMEOUT_SECONDS,
+            withdraw_delay: WITHDRAW_DELAY_SECONDS,
+            enabled_kinds: BetKind::ALL,
+            profit_share_bps: 1_000,
+            operator_fee_bps: 100,
+            crank_tip: 0,
+            max_number_exposure: 0,
+            max_payout_bps: 0,
+        };
+        assert!(!config_harms_players(&current, &current));
+        assert!(!config_harms_players(&current, &TableConfig { min_bet: 5, max_bet: 200, ..current }));
+        assert!(!config_harms_players(&current, &TableConfig { bet_timeout: 60, ..current }));
+        assert!(validate_table_config(&current).is_ok());
+        assert!(validate_table_config(&TableConfig { bet_timeout: 0, ..current }).is_err());
+        assert!(validate_table_config(&TableConfig { bet_timeout: MAX_BET_TIMEOUT_SECONDS + 1, ..current }).is_err());
+        assert!(validate_table_config(&TableConfig { withdraw_delay: 0, ..current }).is_err());
+        assert!(
+            validate_table_config(&TableConfig { withdraw_delay: MAX_WITHDRAW_DELAY_SECONDS + 1, ..current }).is_err()
+        );
+
+        assert!(config_harms_players(&current, &TableConfig { min_bet: 11, ..current }));
+        assert!(config_harms_players(&current, &TableConfig { max_bet: 99, ..current }));
+        assert!(config_harms_players(&current, &TableConfig { bet_timeout: BET_TIMEOUT_SECONDS + 1, ..current }));
+        assert!(config_harms_players(&current, &TableConfig { withdraw_delay: 0, ..current }));
+        assert!(config_harms_players(
+            &current,
+            &TableConfig { enabled_kinds: BetKind::ALL & !BetKind::Red.bit(), ..current }
+        ));
+        assert!(!config_harms_players(&current, &TableConfig { operator_fee_bps: 99, ..current }));
+        assert!(config_harms_players(&current, &TableConfig { operator_fee_bps: 101, ..current }));
+        assert!(config_harms_players(&current, &TableConfig { crank_tip: 1, ..current }));
+        assert!(config_harms_players(&current, &TableConfig { max_number_exposure: 1_000, ..current }));
+        let capped = TableConfig { max_number_exposure: 1_000, ..current };
+        assert!(!config_harms_players(&capped, &TableConfig { max_number_exposure: 2_000, ..capped }));
+        assert!(!config_harms_players(&capped, &current));
+        assert!(config_harms_players(&capped, &TableConfig { max_number_exposure: 999, ..capped }));
+        assert!(config_harms_players(&current, &TableConfig { max_payout_bps: 500, ..current }));
+        assert!(config_harms_players(&current, &TableConfig { profit_share_bps: 999, ..current }));
+        assert!(config_harms_players(&current, &TableConfig { profit_share_bps: 1_001, ..current }));
+
+        // Operator instructions and passed proposals go through the same timelocks
+        let mut table = Table { mode: TableMode::Public, ..Default::default() };
+        table.apply_config(&current);
+        let eta = 1_000 + WITHDRAW_DELAY_SECONDS;
+        assert_eq!(change_config(&mut table, TableConfig { min_bet: 11, ..current }, 1_000).unwrap(), Some(eta));
+        assert_eq!(table.min_bet, 10);
+        assert_eq!(change_config(&mut table, TableConfig { min_bet: 5, ..current }, 1_000).unwrap(), None);
+        assert_eq!((table.min_bet, table.config_request_ts), (5, 0));
+        assert!(change_config(&mut table, TableConfig { bet_timeout: 0, ..current }, 1_000).is_err());
+
+        assert_eq!(change_mode(&mut table, TableMode::Private, 1_000), Some(eta));
+        assert_eq!((table.mode, table.pending_mode), (TableMode::Public, TableMode::Private));
+        assert_eq!(change_mode(&mut table, TableMode::Public, 1_000), None);
+        assert_eq!(table.mode_request_ts, 0);
+    }
+
+    #[test]
+    fn operator_threshold_scales_with_gov_supply() {
+        // The original fixed 51-of-100 rule
+        assert_eq!(bps_of(100, 5_100).unwrap(), 51);
+        // Same fraction of a 6-decimal mint
+        assert_eq!(bps_of(1_000_000 * 1_000_000, 5_100).unwrap(), 510_000_000_000);
+        // Rounds up so the threshold stays a strict majority
+        assert_eq!(bps_of(3, 5_001).unwrap(), 2);
+        assert_eq!(bps_of(u64::MAX, BPS_DENOMINATOR).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn lockups_boost_voting_weight_until_they_end() {
+        assert_eq!(lock_boost_bps(0), 10_000);
+        assert_eq!(lock_boost_bps(MAX_GOV_LOCK_SECONDS / 2), 15_000);
+        assert_eq!(lock_boost_bps(MAX_GOV_LOCK_SECONDS), 20_000);
+        assert_eq!(lock_boost_bps(10 * MAX_GOV_LOCK_SECONDS), 20_000);
+
+        let dep = GovDeposit {
+            amount: 40,
+            lock_until: 1_000,
+            lock_boost_bps: 15_000,
+            ..Default::default()
+        };
+        assert_eq!(voting_weight(&dep, 999).unwrap(), 60);
+        assert_eq!(voting_weight(&dep, 1_000).unwrap(), 40);
+    }
+
+    #[test]
+    fn delegated_deposits_move_power_to_the_delegate() {
+        let holder = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let mut own = GovDeposit {
+            depositor: holder,
+            amount: 30,
+            ..Default::default()
+        };
+        let power = holder_power(&own, &holder, &[], 0).unwrap();
+        assert_eq!((power.control, power.voting), (30, 30));
+
+        own.delegate = delegate;
+        let power = holder_power(&own, &holder, &[], 0).unwrap();
+        assert_eq!((power.control, power.voting), (0, 0));
+
+        // Only GOV still counted for the operator is guarded against leaving
+        let table = Table { operator: holder, ..Default::default() };
+        assert!(!backs_operator(&table, &own));
+        own.delegate = Pubkey::default();
+        assert!(backs_operator(&table, &own));
+        let delegator = GovDeposit { depositor: delegate, delegate: holder, ..Default::default() };
+        assert!(backs_operator(&table, &delegator));
+    }
+
+    #[test]
+    fn repair_carries_over_decodable_global_state() {
+        let pauser = Pubkey::new_unique();
+        let current = GlobalState {
+            usdc_mint: Pubkey::new_unique(),
+            vault_usdc: Pubkey::new_unique(),
+            total_locked_liability: 700,
+            total_active_bets: 3,
+            admin: Pubkey::new_unique(),
+            pending_admin: Pubkey::default(),
+            pauser,
+            fee_manager: Pubkey::default(),
+            paused: true,
+            liquidity_floor: 50,
+            protocol_fee_bps: 200,
+            protocol_fees: 90,
+            bumps: GlobalBumps { global: 1, vault_usdc: 2 },
+        };
+        let mut data = Vec::new();
+        current.try_serialize(&mut data).unwrap();
+        let gs = decode_previous_global(&data);
+        assert_eq!((gs.total_locked_liability, gs.total_active_bets, gs.protocol_fees), (700, 3, 90));
+        assert!(gs.paused && gs.pauser == pauser);
+
+        // The original layout stops after the active bet count and the bumps
+        let mut data = anchor_account_discriminator("GlobalState").to_vec();
+        data.extend_from_slice(&[[7u8; 32], [8u8; 32]].concat());
+        data.extend_from_slice(&400u64.to_le_bytes());
+        data.extend_from_slice(&2u64.to_le_bytes());
+        data.extend_from_slice(&[1, 2]);
+        let gs = decode_previous_global(&data);
+        assert_eq!((gs.total_locked_liability, gs.total_active_bets), (400, 2));
+        assert!(!gs.paused && gs.pauser == Pubkey::default());
+
+        assert_eq!(decode_previous_global(&[0u8; 4]).total_locked_liability, 0);
+    }
+
+    #[test]
+    fn house_profit_is_shared_pro_rata_after_losses_are_recovered() {
+        let mut table = Table {
+            liquidity: 10_000,
+            profit_share_bps: 2_000,
+            total_gov_deposited: 100,
+            ..Default::default()
+        };
+        let mut a = GovDeposit { amount: 75, ..Default::default() };
+        let mut b = GovDeposit { amount: 25, ..Default::default() };
+
+        // A player win leaves a deficit that later profit must cover first
+        distribute_house_pnl(&mut table, -300).unwrap();
+        assert_eq!(table.reward_pool, 0);
+        distribute_house_pnl(&mut table, 200).unwrap();
+        assert_eq!(table.reward_pool, 0);
+        assert_eq!(table.undistributed_pnl, -100);
+
+        // 1_100 - 100 = 1_000 of net profit, 20% of it shared
+        distribute_house_pnl(&mut table, 1_100).unwrap();
+        assert_eq!(table.reward_pool, 200);
+        assert_eq!(table.liquidity, 9_800);
+        assert_eq!(table.undistributed_pnl, 0);
+
+        sync_rewards(&mut table, &mut a).unwrap();
+        sync_rewards(&mut table, &mut b).unwrap();
+        assert_eq!((a.pending_rewards, b.pending_rewards), (150, 50));
+        assert_eq!(table.rewards_owed, 200);
+
+        // Syncing again books nothing new
+        sync_rewards(&mut table, &mut a).unwrap();
+        assert_eq!(a.pending_rewards, 150);
+
+        // Profit made while nobody had GOV deposited is not shared with later depositors
+        table.total_gov_deposited = 0;
+        distribute_house_pnl(&mut table, 5_000).unwrap();
+        table.total_gov_deposited = 100;
+        distribute_house_pnl(&mut table, 0).unwrap();
+        assert_eq!(table.reward_pool, 200);
+        assert_eq!(table.undistributed_pnl, 0);
+    }
+
+    fn sample_kinds() -> Vec<BetKind> {
+        vec![
+            BetKind::Straight { number: 17 },
+            BetKind::Split { a: 17, b: 20 },
+            BetKind::Street { row: 5 },
+            BetKind::Corner { row: 5, col: 1 },
+            BetKind::SixLine { row: 5 },
+            BetKind::Red,
+            BetKind::Black,
+            BetKind::Even,
+            BetKind::Odd,
+            BetKind::Low,
+            BetKind::High,
+            BetKind::Dozen { idx: 1 },
+            BetKind::Column { idx: 1 },
+            BetKind::Split { a: 0, b: 3 },
+            BetKind::Trio { a: 0, b: 2, c: 3 },
+            BetKind::Basket,
+        ]
+    }
+
+    #[test]
+    fn fees_are_taken_from_winnings_for_every_kind() {
+        let stake = 1_000_000;
+        for kind in sample_kinds() {
+            validate_bet_kind(&kind, TableVariant::European).unwrap();
+            let chips = vec![BetChip { kind: kind.clone(), stake }];
+            let mult = bet_multiplier(&kind) as u64;
+            let win = (0..TableVariant::European.pockets()).find(|n| bet_covers_number(&kind, *n)).unwrap();
+            let lose = (0..TableVariant::European.pockets()).find(|n| !bet_covers_number(&kind, *n)).unwrap();
+
+            let payout = chips_payout(&chips, win, TableVariant::European, false).unwrap();
+            assert_eq!(payout, stake * (mult + 1));
+            let fees = winnings_fees(stake, payout, 250, MAX_FEE_BPS).unwrap();
+            assert_eq!(fees.protocol, stake * mult * 250 / 10_000);
+            assert_eq!(fees.operator, stake * mult * MAX_FEE_BPS as u64 / 10_000);
+            assert!(fees.protocol + fees.operator < payout - stake);
+
+            let payout = chips_payout(&chips, lose, TableVariant::European, false).unwrap();
+            assert_eq!(winnings_fees(stake, payout, 250, MAX_FEE_BPS).unwrap(), Fees::default());
+        }
+    }
+
+    #[test]
+    fn fees_only_apply_to_net_winnings_of_a_layout() {
+        // Red + Black on a red number returns exactly the total stake: no winnings, no fee
+        let chips = vec![
+            BetChip { kind: BetKind::Red, stake: 100 },
+            BetChip { kind: BetKind::Black, stake: 100 },
+        ];
+        let payout = chips_payout(&chips, 1, TableVariant::European, false).unwrap();
+        assert_eq!(payout, 200);
+        assert_eq!(winnings_fees(200, payout, 500, 500).unwrap(), Fees::default());
+
+        // Straight 17 + Black on 17: 3_600 + 200 back on 200 staked
+        let chips = vec![
+            BetChip { kind: BetKind::Straight { number: 17 }, stake: 100 },
+            BetChip { kind: BetKind::Black, stake: 100 },
+        ];
+        let payout = chips_payout(&chips, 17, TableVariant::European, false).unwrap();
+        assert_eq!(payout, 3_800);
+        let fees = winnings_fees(200, payout, 100, 200).unwrap();
+        assert_eq!(fees, Fees { protocol: 36, operator: 72 });
+    }
+
+    #[test]
+    fn opposing_bets_only_lock_the_worst_outcome() {
+        let uncapped = NumberCaps::default();
+        let red = [BetChip { kind: BetKind::Red, stake: 100 }];
+        let black = [BetChip { kind: BetKind::Black, stake: 100 }];
+
+        let exposure = exposure_after(&Exposure::default(), &red, TableVariant::European, 200, 0, &uncapped).unwrap();
+        assert_eq!(exposure.max(), 200);
+        // Summed liability would need 400; per-number exposure still tops out at 200
+        let mut exposure = exposure_after(&exposure, &black, TableVariant::European, 200, 0, &uncapped).unwrap();
+        assert_eq!(exposure.max(), 200);
+        assert_eq!(exposure.payouts[0], 0);
+        assert!(exposure_after(&exposure, &red, TableVariant::European, 399, 0, &uncapped).is_err());
+
+        exposure.remove(&red, TableVariant::European, false).unwrap();
+        exposure.remove(&black, TableVariant::European, false).unwrap();
+        assert_eq!(exposure, Exposure::default());
+    }
+
+    #[test]
+    fn number_cap_only_checks_numbers_the_bet_pays_on() {
+        let uncapped = NumberCaps::default();
+        let straight = |number| [BetChip { kind: BetKind::Straight { number }, stake: 10 }];
+        let exposure = exposure_after(&Exposure::default(), &straight(17), TableVariant::European, 10_000, 0, &uncapped).unwrap();
+        assert_eq!(exposure.payouts[17], 360);
+
+        assert!(exposure_after(&exposure, &straight(17), TableVariant::European, 10_000, 700, &uncapped).is_err());
+        assert!(exposure_after(&exposure, &straight(17), TableVariant::European, 10_000, 720, &uncapped).is_ok());
+        // 17 already sits above a lowered cap, but a bet on 5 does not add to it
+        assert!(exposure_after(&exposure, &straight(5), TableVariant::European, 10_000, 100, &uncapped).is_err());
+        assert!(exposure_after(&exposure, &straight(5), TableVariant::European, 10_000, 360, &uncapped).is_ok());
+
+        // A number's own cap applies on top of the table-wide one
+        let mut caps = NumberCaps::default();
+        caps.caps[5] = 200;
+        assert!(exposure_after(&exposure, &straight(5), TableVariant::European, 10_000, 360, &caps).is_err());
+        assert!(exposure_after(&exposure, &straight(4), TableVariant::European, 10_000, 360, &caps).is_ok());
+        assert!(exposure_after(&exposure, &straight(5), TableVariant::European, 10_000, 0, &caps).is_err());
+        assert!(number_caps_tightened(&NumberCaps::default(), &caps));
+        assert!(!number_caps_tightened(&caps, &uncapped));
+    }
+
+    #[test]
+    fn shares_are_priced_around_pending_bets() {
+        let mut table = Table { liquidity: 1_000, total_shares: 500, ..Default::default() };
+        assert_eq!(shares_for_deposit(&table, 100).unwrap(), 50);
+        assert_eq!(assets_for_shares(&table, 50).unwrap(), 100);
+
+        // A pending bet doesn't block exits: redemptions pay out of the unlocked part only,
+        // while deposits still buy in at the full NAV
+        table.active_bets = 1;
+        table.locked_liability = 200;
+        assert_eq!(assets_for_shares(&table, 50).unwrap(), 80);
+        assert_eq!(assets_for_shares(&table, 500).unwrap(), table_available_liquidity(&table));
+        assert_eq!(shares_for_withdraw(&table, 80).unwrap(), 50);
+        assert_eq!(shares_for_deposit(&table, 100).unwrap(), 50);
+
+        // Worthless shares don't block new capital
+        table.active_bets = 0;
+        table.locked_liability = 0;
+        table.liquidity = 0;
+        assert_eq!(shares_for_deposit(&table, 100).unwrap(), 50_000);
+
+        // Liquidity nobody holds shares in goes to the operator, not the next depositor
+        let mut table = Table { liquidity: 30, ..Default::default() };
+        adopt_orphaned_liquidity(&mut table);
+        assert_eq!((table.operator_shares, table.total_shares), (30, 30));
+        assert_eq!(shares_for_deposit(&table, 100).unwrap(), 100);
+    }
+
+    #[test]
+    fn bet_limits_follow_free_liquidity() {
+        let mut table = Table {
+            min_bet: 10,
+            max_bet: 1_000_000,
+            enabled_kinds: BetKind::ALL & !BetKind::Column { idx: 0 }.bit(),
+            liquidity: 1_000_000,
+            locked_liability: 280_000,
+            max_payout_bps: 500,
+            ..Default::default()
+        };
+        // 5% of 720_000 free = 36_000 of payout per bet
+        let limits = bet_limits_for(&table);
+        assert_eq!(limits.len(), 16);
+        let stake_of = |limits: &[BetLimit], kind: BetKind| {
+            limits.iter().find(|l| l.kind == kind.bit()).unwrap().max_stake
+        };
+        assert_eq!(stake_of(&limits, BetKind::Straight { number: 7 }), 1_000);
+        assert_eq!(stake_of(&limits, BetKind::Red), 18_000);
+        assert_eq!(stake_of(&limits, BetKind::Dozen { idx: 2 }), 12_000);
+        assert_eq!(stake_of(&limits, BetKind::Column { idx: 1 }), 0);
+        assert_eq!(risk_payout_limit(&table), 36_000);
+
+        // Without a policy the whole free liquidity bounds a bet, then the fixed max_bet
+        table.max_payout_bps = 0;
+        assert_eq!(stake_of(&bet_limits_for(&table), BetKind::Straight { number: 7 }), 20_000);
+        assert_eq!(stake_of(&bet_limits_for(&table), BetKind::Red), 360_000);
+        table.max_bet = 5_000;
+        assert_eq!(stake_of(&bet_limits_for(&table), BetKind::Red), 5_000);
+
+        // Below the minimum nothing can be bet
+        table.locked_liability = 999_700;
+        assert_eq!(stake_of(&bet_limits_for(&table), BetKind::Straight { number: 7 }), 0);
+    }
+
+    /// Pockets a kind wins on, checked against every pocket of the wheel.
+    fn covered(kind: &BetKind, variant: TableVariant) -> Vec<u8> {
+        (0..variant.pockets()).filter(|n| bet_covers_number(kind, *n)).collect()
+    }
+
+    #[test]
+    fn zero_adjacent_bets_cover_exactly_their_numbers() {
+        let euro = TableVariant::European;
+        let american = TableVariant::American;
+        let cases: [(BetKind, TableVariant, &[u8], u64); 12] = [
+            (BetKind::Split { a: 0, b: 1 }, euro, &[0, 1], 17),
+            (BetKind::Split { a: 2, b: 0 }, euro, &[0, 2], 17),
+            (BetKind::Split { a: 0, b: 3 }, euro, &[0, 3], 17),
+            (BetKind::Split { a: 0, b: DOUBLE_ZERO }, american, &[0, DOUBLE_ZERO], 17),
+            (BetKind::Split { a: DOUBLE_ZERO, b: 3 }, american, &[3, DOUBLE_ZERO], 17),
+            (BetKind::Trio { a: 0, b: 1, c: 2 }, euro, &[0, 1, 2], 11),
+            (BetKind::Trio { a: 3, b: 2, c: 0 }, euro, &[0, 2, 3], 11),
+            (BetKind::Trio { a: 0, b: DOUBLE_ZERO, c: 2 }, american, &[0, 2, DOUBLE_ZERO], 11),
+            (BetKind::Trio { a: 2, b: 3, c: DOUBLE_ZERO }, american, &[2, 3, DOUBLE_ZERO], 11),
+            (BetKind::Basket, euro, &[0, 1, 2, 3], 8),
+            (BetKind::Basket, TableVariant::EnPrison, &[0, 1, 2, 3], 8),
+            (BetKind::TopLine, american, &[0, 1, 2, 3, DOUBLE_ZERO], 6),
+        ];
+        for (kind, variant, numbers, mult) in cases {
+            validate_bet_kind(&kind, variant).unwrap();
+            assert_eq!(covered(&kind, variant), numbers.to_vec());
+            assert_eq!(bet_multiplier(&kind) as u64, mult);
+
+            let chips = [BetChip { kind, stake: 10 }];
+            for n in 0..variant.pockets() {
+                let expected = if numbers.contains(&n) { 10 * (mult + 1) } else { 0 };
+                assert_eq!(chips_payout(&chips, n, variant, false).unwrap(), expected);
+            }
+            // Priced as on a 36-number wheel (the top line even lower), so zeros keep the edge
+            assert!((mult + 1) * numbers.len() as u64 <= 36);
+        }
+    }
+
+    #[test]
+    fn zero_adjacent_bets_are_limited_to_their_wheel() {
+        let euro = TableVariant::European;
+        let american = TableVariant::American;
+        let rejected: [(BetKind, TableVariant); 10] = [
+            (BetKind::Split { a: 0, b: 4 }, euro),
+            (BetKind::Split { a: 0, b: 0 }, euro),
+            (BetKind::Split { a: 0, b: 3 }, american),
+            (BetKind::Split { a: 0, b: DOUBLE_ZERO }, euro),
+            (BetKind::Split { a: 1, b: DOUBLE_ZERO }, american),
+            (BetKind::Trio { a: 0, b: 1, c: 3 }, euro),
+            (BetKind::Trio { a: 0, b: 2, c: 3 }, american),
+            (BetKind::Trio { a: 1, b: 2, c: 3 }, euro),
+            (BetKind::Basket, american),
+            (BetKind::TopLine, euro),
+        ];
+        for (kind, variant) in rejected {
+            assert!(validate_bet_kind(&kind, variant).is_err(), "{:?} on {:?}", kind.bit(), variant);
+        }
+
+        // Every split touching a zero is one of the layout's zero splits
+        for variant in [euro, american] {
+            for a in 0..variant.pockets() {
+                for b in 0..variant.pockets() {
+                    let split = BetKind::Split { a, b };
+                    let is_zero_split = zero_splits(variant).contains(&[a.min(b), a.max(b)]);
+                    if is_zero_pocket(a) || is_zero_pocket(b) {
+                        assert_eq!(validate_bet_kind(&split, variant).is_ok(), is_zero_split);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn announced_bets_expand_by_wheel_order() {
+        let euro = TableVariant::European;
+        let numbers = |call: AnnouncedBet| {
+            let chips = expand_announced_bet(&call, 10, euro).unwrap();
+            for chip in chips.iter() {
+                validate_bet_kind(&chip.kind, euro).unwrap();
+            }
+            let covered: Vec<u8> = (0..euro.pockets())
+                .filter(|n| chips.iter().any(|c| bet_covers_number(&c.kind, *n)))
+                .collect();
+            (chips, covered)
+        };
+
+        let (voisins, voisins_numbers) = numbers(AnnouncedBet::VoisinsDuZero);
+        assert_eq!(voisins.iter().map(|c| c.stake).sum::<u64>(), 90);
+        assert_eq!(voisins_numbers, [0, 2, 3, 4, 7, 12, 15, 18, 19, 21, 22, 25, 26, 28, 29, 32, 35]);
+        let (tiers, tiers_numbers) = numbers(AnnouncedBet::Tiers);
+        assert_eq!(tiers.len(), 6);
+        assert_eq!(tiers_numbers, [5, 8, 10, 11, 13, 16, 23, 24, 27, 30, 33, 36]);
+        let (orphelins, orphelins_numbers) = numbers(AnnouncedBet::Orphelins);
+        assert_eq!(orphelins.iter().map(|c| c.stake).sum::<u64>(), 50);
+        assert_eq!(orphelins_numbers, [1, 6, 9, 14, 17, 20, 31, 34]);
+
+        // The three sectors split the wheel between them
+        let mut all = [voisins_numbers, tiers_numbers, orphelins_numbers].concat();
+        all.sort_unstable();
+        assert_eq!(all, (0..37).collect::<Vec<u8>>());
+
+        // Voisins on 0 pays the doubled trio; on 17 the two Orphelins splits both win
+        assert_eq!(chips_payout(&voisins, 0, euro, false).unwrap(), 20 * 12);
+        assert_eq!(chips_payout(&orphelins, 17, euro, false).unwrap(), 2 * 10 * 18);
+        assert_eq!(chips_max_payout(&orphelins, euro).unwrap(), 360);
+
+        // "5 and 2 neighbours" follows the wheel, wrapping around 0
+        let (five, five_numbers) = numbers(AnnouncedBet::Neighbours { number: 5, neighbours: 2 });
+        assert_eq!(five.len(), 5);
+        assert_eq!(five_numbers, [5, 10, 16, 23, 24]);
+        let (_, zero_numbers) = numbers(AnnouncedBet::Neighbours { number: 0, neighbours: 1 });
+        assert_eq!(zero_numbers, [0, 26, 32]);
+        let (wide, _) = numbers(AnnouncedBet::Neighbours { number: 26, neighbours: 7 });
+        assert_eq!(wide.len(), BetAccount::MAX_CHIPS - 1);
+
+        for call in [
+            AnnouncedBet::VoisinsDuZero,
+            AnnouncedBet::Tiers,
+            AnnouncedBet::Orphelins,
+            AnnouncedBet::Neighbours { number: 5, neighbours: 2 },
+        ] {
+            assert_eq!(expand_announced_bet(&call, 10, euro).unwrap().len(), call.chip_count());
+            assert!(expand_announced_bet(&call, 10, TableVariant::American).is_err());
+        }
+        assert!(expand_announced_bet(&AnnouncedBet::Neighbours { number: 5, neighbours: 0 }, 10, euro).is_err());
+        assert!(expand_announced_bet(&AnnouncedBet::Neighbours { number: 5, neighbours: 8 }, 10, euro).is_err());
+        assert!(expand_announced_bet(&AnnouncedBet::Neighbours { number: 37, neighbours: 1 }, 10, euro).is_err());
+    }
+
+    #[test]
+    fn american_wheel_adds_double_zero() {
+        let uncapped = NumberCaps::default();
+        let american = TableVariant::American;
+        assert!(validate_bet_kind(&BetKind::Straight { number: DOUBLE_ZERO }, american).is_ok());
+        assert!(validate_bet_kind(&BetKind::Straight { number: DOUBLE_ZERO }, TableVariant::European).is_err());
+
+        let mut rnd = [0u8; 64];
+        rnd[0] = 75; // 75 % 38 == 37, 75 % 37 == 1
+        assert_eq!(roulette_number_from_randomness(&rnd, american), DOUBLE_ZERO);
+        assert_eq!(roulette_number_from_randomness(&rnd, TableVariant::European), 1);
+
+        // Every bet but a straight on 00 loses on it
+        for kind in sample_kinds() {
+            let chips = [BetChip { kind, stake: 100 }];
+            assert_eq!(chips_payout(&chips, DOUBLE_ZERO, american, false).unwrap(), 0);
+        }
+        let chips = [BetChip { kind: BetKind::Straight { number: DOUBLE_ZERO }, stake: 100 }];
+        assert_eq!(chips_payout(&chips, DOUBLE_ZERO, american, false).unwrap(), 3_600);
+        let exposure = exposure_after(&Exposure::default(), &chips, american, 10_000, 0, &uncapped).unwrap();
+        assert_eq!(exposure.payouts[DOUBLE_ZERO as usize], 3_600);
+    }
+
+    #[test]
+    fn la_partage_returns_half_of_even_money_bets_on_zero() {
+        let variant = TableVariant::LaPartage;
+        let chips = [
+            BetChip { kind: BetKind::Red, stake: 101 },
+            BetChip { kind: BetKind::Dozen { idx: 1 }, stake: 100 },
+        ];
+        assert_eq!(chips_payout(&chips, 0, variant, false).unwrap(), 50);
+        assert_eq!(chips_payout(&chips, 0, TableVariant::European, false).unwrap(), 0);
+        assert_eq!(chips_payout(&chips, 1, variant, false).unwrap(), 202 + 300);
+        assert_eq!(chips_payout(&chips, 2, variant, false).unwrap(), 300);
+    }
+
+    #[test]
+    fn en_prison_holds_even_money_bets_for_the_next_spin() {
+        let variant = TableVariant::EnPrison;
+        let red = BetChip { kind: BetKind::Red, stake: 100 };
+        let zero = BetChip { kind: BetKind::Straight { number: 0 }, stake: 10 };
+        let chips = [red.clone(), zero.clone()];
+
+        // On zero the straight wins, red is imprisoned rather than lost
+        assert_eq!(chips_payout(&chips, 0, variant, false).unwrap(), 360);
+        assert!(goes_to_prison(&red, 0, variant, false));
+        assert!(!goes_to_prison(&zero, 0, variant, false));
+        assert!(!goes_to_prison(&red, 0, TableVariant::European, false));
+        // Zero stays locked for the stake the prisoner may still win back
+        assert_eq!(chips_exposure(&chips, 0, variant, false).unwrap(), 460);
+        assert_eq!(chips_max_payout(&chips, variant).unwrap(), 460);
+
+        // The next spin only returns the stake on a win, and a second zero loses it
+        let prisoner = [red];
+        assert_eq!(chips_payout(&prisoner, 1, variant, true).unwrap(), 100);
+        assert_eq!(chips_payout(&prisoner, 2, variant, true).unwrap(), 0);
+        assert_eq!(chips_payout(&prisoner, 0, variant, true).unwrap(), 0);
+        assert!(!goes_to_prison(&prisoner[0], 0, variant, true));
+    }
+
+    #[test]
+    fn imprisoned_round_bets_move_from_open_to_prison_until_spun() {
+        let round_key = Pubkey::new_unique();
+        let mut round = Round {
+            table: Pubkey::default(),
+            round_id: 0,
+            state: RoundState::Settled,
+            opened_ts: 0,
+            closes_ts: 0,
+            bet_timeout: BET_TIMEOUT_SECONDS,
+            force: [0u8; 32],
+            randomness_account: Pubkey::default(),
+            result_number: Some(0),
+            bet_count: 2,
+            open_bets: 2,
+            imprisoned_bets: 0,
+        };
+        let bet = |state| BetAccount {
+            table: Pubkey::default(),
+            player: Pubkey::default(),
+            stake: 100,
+            max_total_payout: 100,
+            chips: vec![BetChip { kind: BetKind::Red, stake: 100 }],
+            state,
+            created_ts: 0,
+            bet_timeout: BET_TIMEOUT_SECONDS,
+            force: [0u8; 32],
+            randomness_account: Pubkey::default(),
+            result_number: Some(0),
+            round: round_key,
+            tip: 0,
+        };
+
+        round_bet_settled(&mut round, &bet(BetState::Resolved));
+        assert_eq!((round.open_bets, round.imprisoned_bets), (1, 0));
+        let mut prisoner = bet(BetState::Imprisoned);
+        round_bet_settled(&mut round, &prisoner);
+        assert_eq!((round.open_bets, round.imprisoned_bets), (0, 1));
+
+        // Spinning or releasing needs the round back, then the prisoner stands alone
+        assert!(prisoner_leaves_round(None, &mut prisoner).is_err());
+        prisoner_leaves_round(Some(&mut round), &mut prisoner).unwrap();
+        assert_eq!((round.open_bets, round.imprisoned_bets), (0, 0));
+        assert_eq!(prisoner.round, Pubkey::default());
+        prisoner_leaves_round(None, &mut prisoner).unwrap();
+        assert_eq!(round.bet_count, 2);
+    }
+
+    #[test]
+    fn proposals_need_majority_and_quorum() {
+        let tally = |yes_votes, no_votes, turnout| Proposal {
+            table: Pubkey::default(),
+            proposer: Pubkey::default(),
+            id: 0,
+            action: ProposalAction::SetPaused { paused: true },
+            created_ts: 0,
+            voting_ends_ts: 0,
+            quorum: 30,
+            yes_votes,
+            no_votes,
+            turnout,
+            executed: false,
+        };
+        assert!(proposal_passed(&tally(30, 0, 30)));
+        assert!(proposal_passed(&tally(20, 10, 30)));
+        assert!(!proposal_passed(&tally(29, 0, 29)));
+        assert!(!proposal_passed(&tally(20, 20, 40)));
+        assert!(!proposal_passed(&tally(10, 40, 50)));
+        // Lockup boosts weigh in the majority but cannot make up for missing turnout
+        assert!(!proposal_passed(&tally(30, 0, 15)));
+    }
+
+    #[test]
+    fn refund_of_fulfilled_bet_settles_with_outcome() {
+        let mut rnd = [0u8; 64];
+        rnd[0] = 40; // 40 % 37 == 3
+        for now in [1_000, 1_000 + BET_TIMEOUT_SECONDS, 1_000 + 10 * BET_TIMEOUT_SECONDS] {
+            assert_eq!(
+                refund_decision(Some(rnd), TableVariant::European, 1_000, BET_TIMEOUT_SECONDS, now).unwrap(),
+                RefundDecision::Settle(3)
+            );
+        }
+    }
 }
@@ -0,0 +1,256 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import {
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import assert from "assert";
+import crypto from "crypto";
+
+describe("roulette_table (devnet) account binding for resolve/refund", () => {
+  process.env.ANCHOR_PROVIDER_URL ??= "https://api.devnet.solana.com";
+  process.env.ANCHOR_WALLET ??= "target/deploy/deployer-keypair.json";
+
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.RouletteTable as Program<any>;
+  const payer = provider.wallet as anchor.Wallet;
+
+  const ORAO_PROGRAM_ID = new PublicKey("VRFzZoJdhFWL8rkvu87LpKM3RbcVezpMEc6X5GVDr7y");
+  const CONFIG_ACCOUNT_SEED = Buffer.from("orao-vrf-network-configuration");
+  const RANDOMNESS_ACCOUNT_SEED = Buffer.from("orao-vrf-randomness-request");
+  const BPF_LOADER_UPGRADEABLE_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+  // init_global is restricted to the program's upgrade authority
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_ID
+  );
+  const TOKEN_PROGRAM_ID = anchor.utils.token.TOKEN_PROGRAM_ID;
+
+  function errorToString(e: any): string {
+    if (!e) return "";
+    if (typeof e === "string") return e;
+    if (e instanceof Error) return `${e.name}: ${e.message}`;
+    try {
+      return JSON.stringify(e);
+    } catch {
+      return String(e);
+    }
+  }
+
+  async function expectError(p: Promise<any>, expected: string) {
+    try {
+      await p;
+    } catch (e: any) {
+      const msg = errorToString(e) + " " + (e?.logs ?? []).join("\n");
+      assert.ok(msg.includes(expected), `expected "${expected}", got: ${msg}`);
+      return;
+    }
+    assert.fail(`expected failure with "${expected}"`);
+  }
+
+  async function setupGlobal(usdcMint: PublicKey) {
+    const [globalState] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global"), usdcMint.toBuffer()],
+      program.programId
+    );
+    const [globalVaultUsdc] = PublicKey.findProgramAddressSync(
+      [Buffer.from("global_vault_usdc"), globalState.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .initGlobal()
+      .accounts({
+        payer: payer.publicKey,
+        usdcMint,
+        globalState,
+        globalVaultUsdc,
+        program: program.programId,
+        programData,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    return { globalState, globalVaultUsdc };
+  }
+
+  async function setupTable(usdcMint: PublicKey, govMint: PublicKey, globalState: PublicKey) {
+    const seed = new anchor.BN(Date.now() + Math.floor(Math.random() * 1_000));
+    const [table] = PublicKey.findProgramAddressSync(
+      [Buffer.from("table"), payer.publicKey.toBuffer(), seed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const [controlVaultGov] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_gov"), table.toBuffer()],
+      program.programId
+    );
+    await program.methods
+      .createTable(seed, { private: {} } as any, { european: {} } as any, new anchor.BN(10_000), new anchor.BN(500_000_000), 5_100)
+      .accounts({
+        creator: payer.publicKey,
+        usdcMint,
+        govMint,
+        table,
+        controlVaultGov,
+        globalState,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .rpc();
+    return table;
+  }
+
+  it("rejects every account substitution on resolve_bet and refund_expired_bet", async () => {
+    const usdcMint = await createMint(provider.connection, payer.payer, payer.publicKey, null, 6);
+    const otherUsdcMint = await createMint(provider.connection, payer.payer, payer.publicKey, null, 6);
+    const govMint = await createMint(provider.connection, payer.payer, payer.publicKey, null, 0);
+
+    const payerGovAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer.payer,
+      govMint,
+      payer.publicKey
+    );
+    await mintTo(provider.connection, payer.payer, govMint, payerGovAta.address, payer.publicKey, 100);
+
+    const payerUsdcAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer.payer,
+      usdcMint,
+      payer.publicKey
+    );
+    await mintTo(provider.connection, payer.payer, usdcMint, payerUsdcAta.address, payer.publicKey, 1_000_000_000);
+
+    // Token accounts an attacker would like payouts redirected to
+    const attacker = Keypair.generate();
+    const attackerUsdcAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer.payer,
+      usdcMint,
+      attacker.publicKey
+    );
+    const payerOtherMintAta = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer.payer,
+      otherUsdcMint,
+      payer.publicKey
+    );
+
+    const main = await setupGlobal(usdcMint);
+    const other = await setupGlobal(otherUsdcMint);
+
+    const table = await setupTable(usdcMint, govMint, main.globalState);
+    const otherTable = await setupTable(usdcMint, govMint, main.globalState);
+
+    await program.methods
+      .depositLiquidityUsdc(new anchor.BN(500_000_000))
+      .accounts({
+        operator: payer.publicKey,
+        operatorUsdcAta: payerUsdcAta.address,
+        table,
+        operatorGovAta: payerGovAta.address,
+        govMint,
+        globalState: main.globalState,
+        globalVaultUsdc: main.globalVaultUsdc,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const [config] = PublicKey.findProgramAddressSync([CONFIG_ACCOUNT_SEED], ORAO_PROGRAM_ID);
+    const configAcc = await provider.connection.getAccountInfo(config, "confirmed");
+    if (!configAcc) throw new Error(`ORAO config account not found: ${config.toBase58()}`);
+    const treasury = new PublicKey(Buffer.from(configAcc.data).subarray(8 + 32, 8 + 64));
+
+    const force = crypto.randomBytes(32);
+    const [random] = PublicKey.findProgramAddressSync([RANDOMNESS_ACCOUNT_SEED, force], ORAO_PROGRAM_ID);
+    const [otherRandom] = PublicKey.findProgramAddressSync(
+      [RANDOMNESS_ACCOUNT_SEED, crypto.randomBytes(32)],
+      ORAO_PROGRAM_ID
+    );
+
+    const tableAcc: any = await (program.account as any)["table"].fetch(table);
+    const [bet] = PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("bet"),
+        table.toBuffer(),
+        payer.publicKey.toBuffer(),
+        new anchor.BN(tableAcc.betSeq).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId
+    );
+
+    await program.methods
+      .placeBet({ red: {} } as any, new anchor.BN(10_000), Array.from(force) as any)
+      .accounts({
+        player: payer.publicKey,
+        playerUsdcAta: payerUsdcAta.address,
+        table,
+        globalState: main.globalState,
+        globalVaultUsdc: main.globalVaultUsdc,
+        bet,
+        random,
+        treasury,
+        config,
+        vrf: ORAO_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const valid = {
+      table,
+      bet,
+      playerUsdcAta: payerUsdcAta.address,
+      globalState: main.globalState,
+      globalVaultUsdc: main.globalVaultUsdc,
+      random,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    const substitutions: [string, Partial<typeof valid>, string][] = [
+      ["foreign table", { table: otherTable }, "Bet does not belong to this table"],
+      [
+        "foreign global state",
+        { globalState: other.globalState, globalVaultUsdc: other.globalVaultUsdc },
+        "Global state does not match the table",
+      ],
+      ["foreign vault", { globalVaultUsdc: other.globalVaultUsdc }, "Vault does not match the global state"],
+      [
+        "attacker payout account",
+        { playerUsdcAta: attackerUsdcAta.address },
+        "Token account is not owned by the bet's player",
+      ],
+      [
+        "payout account of another mint",
+        { playerUsdcAta: payerOtherMintAta.address },
+        "Token account mint does not match the table",
+      ],
+      ["foreign randomness", { random: otherRandom }, "Randomness account does not match the bet"],
+    ];
+
+    for (const [name, patch, expected] of substitutions) {
+      console.log(`resolve_bet with ${name}`);
+      await expectError(
+        program.methods
+          .resolveBet()
+          .accounts({ resolver: payer.publicKey, ...valid, ...patch })
+          .rpc(),
+        expected
+      );
+
+      console.log(`refund_expired_bet with ${name}`);
+      await expectError(
+        program.methods
+          .refundExpiredBet()
+          .accounts({ caller: payer.publicKey, ...valid, ...patch })
+          .rpc(),
+        expected
+      );
+    }
+  });
+});
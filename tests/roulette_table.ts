@@ -22,6 +22,12 @@ describe("roulette_table (devnet) end-to-end", () => {
   const ORAO_PROGRAM_ID = new PublicKey("VRFzZoJdhFWL8rkvu87LpKM3RbcVezpMEc6X5GVDr7y");
   const CONFIG_ACCOUNT_SEED = Buffer.from("orao-vrf-network-configuration");
   const RANDOMNESS_ACCOUNT_SEED = Buffer.from("orao-vrf-randomness-request");
+  const BPF_LOADER_UPGRADEABLE_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+  // init_global is restricted to the program's upgrade authority
+  const [programData] = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    BPF_LOADER_UPGRADEABLE_ID
+  );
 
   const sleep = (ms: number) => new Promise((r) => setTimeout(r, ms));
 
@@ -138,6 +144,8 @@ describe("roulette_table (devnet) end-to-end", () => {
           usdcMint,
           globalState: globalStatePda,
           globalVaultUsdc: globalVaultUsdcPda,
+          program: program.programId,
+          programData,
           systemProgram: SystemProgram.programId,
           tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
           rent: anchor.web3.SYSVAR_RENT_PUBKEY,
@@ -158,7 +166,7 @@ describe("roulette_table (devnet) end-to-end", () => {
     );
 
     await program.methods
-      .createTable(seed, { private: {} } as any, new anchor.BN(10_000), new anchor.BN(500_000_000))
+      .createTable(seed, { private: {} } as any, { european: {} } as any, new anchor.BN(10_000), new anchor.BN(500_000_000), 5_100)
       .accounts({
         creator: payer.publicKey,
         usdcMint,
@@ -178,6 +186,9 @@ describe("roulette_table (devnet) end-to-end", () => {
       .accounts({
         operator: payer.publicKey,
         operatorUsdcAta: payerUsdcAta.address,
+        table: tablePda,
+        operatorGovAta: payerGovAta.address,
+        govMint,
         globalState: globalStatePda,
         globalVaultUsdc: globalVaultUsdcPda,
         tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
@@ -242,5 +253,13 @@ describe("roulette_table (devnet) end-to-end", () => {
       },
       600_000
     );
+
+    // Settled bets can be closed to reclaim their rent
+    await program.methods
+      .closeBet()
+      .accounts({ player: payer.publicKey, bet: betPda })
+      .rpc();
+    const closed = await provider.connection.getAccountInfo(betPda, "confirmed");
+    if (closed) throw new Error(`bet account still exists after close_bet: ${betPda.toBase58()}`);
   });
 });